@@ -0,0 +1,143 @@
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+use quarve::resource::local_storage;
+use serde::Deserialize;
+use icpc_scoreboard::parser::CONTEST_TYPES;
+
+// contests shipped with the app, extended by the user's own catalog.json
+const BUNDLED: &str = include_str!("../data/catalog.json");
+
+#[derive(Deserialize, Clone, PartialEq, Debug)]
+pub struct CatalogEntry {
+    pub name: String,
+    pub year: u32,
+    pub region: String,
+    // one of CONTEST_TYPES
+    pub source: String,
+    pub url: String,
+}
+
+pub fn user_catalog_path() -> PathBuf {
+    local_storage().join("catalog.json")
+}
+
+// entries with an unknown source are left out and reported in skipped
+fn parse(json: &str, skipped: &mut Vec<String>) -> Result<Vec<CatalogEntry>, Box<dyn Error>> {
+    let entries: Vec<CatalogEntry> = serde_json::from_str(json)?;
+
+    Ok(entries.into_iter()
+        .filter(|e| {
+            let known = CONTEST_TYPES.contains(&e.source.as_str());
+            if !known {
+                skipped.push(format!("{} {}: unknown source \"{}\"", e.name, e.year, e.source));
+            }
+            known
+        })
+        .collect())
+}
+
+pub struct Catalog {
+    pub entries: Vec<CatalogEntry>,
+    // problems with individual entries, which are left out
+    pub skipped: Vec<String>,
+}
+
+// user entries come first so they can shadow bundled ones with the same url
+pub fn load() -> Result<Catalog, Box<dyn Error>> {
+    let mut skipped = Vec::new();
+    let mut entries = match fs::read_to_string(user_catalog_path()) {
+        Ok(json) => parse(&json, &mut skipped)
+            .map_err(|e| format!("Invalid {}: {}", user_catalog_path().display(), e))?,
+        Err(_) => Vec::new()
+    };
+
+    let bundled = parse(BUNDLED, &mut skipped)?;
+    merge(&mut entries, bundled);
+    Ok(Catalog { entries, skipped })
+}
+
+// adds the bundled entries whose url the user has not listed, newest first
+fn merge(entries: &mut Vec<CatalogEntry>, bundled: Vec<CatalogEntry>) {
+    for entry in bundled {
+        if !entries.iter().any(|e| e.url == entry.url) {
+            entries.push(entry);
+        }
+    }
+
+    entries.sort_by(|e1, e2| e2.year.cmp(&e1.year).then_with(|| e1.name.cmp(&e2.name)));
+}
+
+// entries matching every word of the query in their name, region, year or source
+pub fn search(entries: &[CatalogEntry], query: &str) -> Vec<CatalogEntry> {
+    let words = query.split_whitespace()
+        .map(|w| w.to_lowercase())
+        .collect::<Vec<_>>();
+
+    entries.iter()
+        .filter(|e| {
+            let haystack = format!("{} {} {} {}", e.name, e.region, e.year, e.source).to_lowercase();
+            words.iter().all(|w| haystack.contains(w))
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{merge, parse, search, CatalogEntry, BUNDLED};
+
+    fn entry(name: &str, year: u32, url: &str) -> CatalogEntry {
+        CatalogEntry {
+            name: name.to_string(),
+            year,
+            region: "Northern Eurasia".to_string(),
+            source: "CODEFORCES".to_string(),
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn bundled() {
+        let mut skipped = Vec::new();
+        let entries = parse(BUNDLED, &mut skipped).unwrap();
+
+        assert!(!entries.is_empty());
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn unknown_sources() {
+        let json = r#"[
+            {"name": "Kept", "year": 2024, "region": "Europe", "source": "CERC", "url": "a"},
+            {"name": "Dropped", "year": 2023, "region": "Europe", "source": "DOMJUDGE", "url": "b"}
+        ]"#;
+        let mut skipped = Vec::new();
+        let entries = parse(json, &mut skipped).unwrap();
+
+        assert_eq!(entries.iter().map(|e| e.name.as_str()).collect::<Vec<_>>(), vec!["Kept"]);
+        assert_eq!(skipped, vec!["Dropped 2023: unknown source \"DOMJUDGE\"".to_string()]);
+
+        assert!(parse("[{\"name\": \"No url\"}]", &mut skipped).is_err());
+    }
+
+    #[test]
+    fn user_entries_first() {
+        let mut entries = vec![entry("Mine", 2019, "b")];
+        merge(&mut entries, vec![entry("Old", 2018, "a"), entry("Shadowed", 2019, "b"), entry("New", 2024, "c")]);
+
+        assert_eq!(entries, vec![entry("New", 2024, "c"), entry("Mine", 2019, "b"), entry("Old", 2018, "a")]);
+    }
+
+    #[test]
+    fn searching() {
+        let entries = vec![entry("ICPC NERC Onsite", 2024, "a"), entry("ICPC NEERC Finals", 2018, "b")];
+        let names = |query| search(&entries, query).into_iter().map(|e| e.name).collect::<Vec<_>>();
+
+        assert_eq!(names(""), vec!["ICPC NERC Onsite", "ICPC NEERC Finals"]);
+        // every word has to match, in any field and any case
+        assert_eq!(names("nerc 2024"), vec!["ICPC NERC Onsite"]);
+        assert_eq!(names("EURASIA codeforces 2018"), vec!["ICPC NEERC Finals"]);
+        assert!(names("nerc 2018").is_empty());
+    }
+}
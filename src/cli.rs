@@ -0,0 +1,95 @@
+use std::error::Error;
+use std::path::Path;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use chrono::DateTime;
+use icpc_scoreboard::clics::contest_id;
+use icpc_scoreboard::export::write_event_feed;
+use icpc_scoreboard::parser::{parse, CONTEST_TYPES};
+use icpc_scoreboard::replay::parse_start;
+use icpc_scoreboard::server::Server;
+use crate::history::name_from_url;
+use crate::session::Session;
+
+const DEFAULT_PORT: u16 = 8080;
+
+pub const USAGE: &str = "usage: icpc_scoreboard serve <contest type> <url> [--port N] [--start HH:MM|+N] [--spoiler-safe]
+       icpc_scoreboard export <contest type> <url> <event-feed.ndjson> [--start 2024-09-19T10:00:00+00:00]";
+
+fn check_contest_type(input: &str) -> Result<String, Box<dyn Error>> {
+    let contest_type = input.to_uppercase();
+    if !CONTEST_TYPES.contains(&contest_type.as_str()) {
+        return Err(format!("Unknown contest type {}, expected one of {}", contest_type, CONTEST_TYPES.join(", ")).into());
+    }
+
+    Ok(contest_type)
+}
+
+// Without --start the replay continues the app's session for the same contest,
+// or starts now if there is none
+pub async fn serve(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [contest_type, url, options @ ..] = args else {
+        return Err(USAGE.into());
+    };
+    let contest_type = check_contest_type(contest_type)?;
+
+    let mut port = DEFAULT_PORT;
+    let mut start = None;
+    let mut spoiler_safe = false;
+    let mut options = options.iter();
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--port" => {
+                port = options.next()
+                    .and_then(|p| p.parse().ok())
+                    .ok_or("--port expects a port number")?;
+            }
+            "--start" => {
+                let input = options.next().ok_or("--start expects HH:MM or +minutes")?;
+                start = Some(parse_start(input, SystemTime::now())?);
+            }
+            "--spoiler-safe" => spoiler_safe = true,
+            other => return Err(format!("Unknown option {}\n{}", other, USAGE).into())
+        }
+    }
+
+    let now = SystemTime::now();
+    let start = start
+        .or_else(|| Session::load_active(now)
+            .into_iter()
+            .find(|s| s.contest_type == contest_type && s.url == *url)
+            .map(|s| s.start))
+        .map_or(now, |start| UNIX_EPOCH + Duration::from_secs(start));
+
+    let sb = parse(&contest_type, url, &|status| eprintln!("{}", status)).await?;
+    let sb = if spoiler_safe { sb.without_spoilers() } else { sb };
+
+    eprintln!("Serving {} teams on http://0.0.0.0:{}", sb.entries.len(), port);
+    let name = name_from_url(url);
+    eprintln!("Standings as json at /standings.json, CLICS contest api at /api/contests/{}", contest_id(&name));
+    Server { sb, name, start }.run(("0.0.0.0", port))?;
+
+    Ok(())
+}
+
+// Writes the contest as a CLICS event feed, starting at --start or now.
+// The replay clock plays no part, the feed always covers the whole contest
+pub async fn export(args: &[String]) -> Result<(), Box<dyn Error>> {
+    let [contest_type, url, output, options @ ..] = args else {
+        return Err(USAGE.into());
+    };
+    let contest_type = check_contest_type(contest_type)?;
+
+    let start = match options {
+        [] => SystemTime::now(),
+        [option, time] if option == "--start" => DateTime::parse_from_rfc3339(time)
+            .map_err(|e| format!("Invalid start time \"{}\": {}", time, e))?
+            .into(),
+        _ => return Err(USAGE.into())
+    };
+
+    let sb = parse(&contest_type, url, &|status| eprintln!("{}", status)).await?;
+    write_event_feed(&sb, &name_from_url(url), start, Path::new(output))?;
+    eprintln!("Wrote {} teams to {}", sb.entries.len(), output);
+
+    Ok(())
+}
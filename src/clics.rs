@@ -0,0 +1,669 @@
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use crate::replay::replay_clock;
+use crate::scoreboard::{problem_label, spread, ProblemStatus, Scoreboard, CONTEST_MINUTES, FREEZE_MINUTES};
+
+// version of the CLICS contest api specification that is followed
+pub const API_VERSION: &str = "2023-06";
+
+const PENALTY_MINUTES: usize = 20;
+// scraped scoreboards do not say what language was used
+const LANGUAGE_ID: &str = "unknown";
+
+#[derive(Serialize)]
+struct Contest {
+    id: String,
+    name: String,
+    formal_name: String,
+    start_time: String,
+    duration: String,
+    scoreboard_freeze_duration: String,
+    scoreboard_type: &'static str,
+    penalty_time: usize,
+}
+
+#[derive(Serialize, Default)]
+struct State {
+    started: Option<String>,
+    frozen: Option<String>,
+    ended: Option<String>,
+    thawed: Option<String>,
+    finalized: Option<String>,
+    end_of_updates: Option<String>,
+}
+
+#[derive(Serialize)]
+struct JudgementType {
+    id: &'static str,
+    name: &'static str,
+    penalty: bool,
+    solved: bool,
+}
+
+#[derive(Serialize)]
+struct Language {
+    id: &'static str,
+    name: &'static str,
+    entry_point_required: bool,
+    extensions: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Problem {
+    id: String,
+    label: String,
+    name: String,
+    ordinal: usize,
+}
+
+#[derive(Serialize)]
+struct Organization {
+    id: String,
+    name: String,
+    formal_name: String,
+}
+
+#[derive(Serialize)]
+struct Team {
+    id: String,
+    name: String,
+    organization_id: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Submission {
+    id: String,
+    language_id: &'static str,
+    problem_id: String,
+    team_id: String,
+    time: String,
+    contest_time: String,
+    files: Vec<Value>,
+}
+
+#[derive(Serialize)]
+struct Judgement {
+    id: String,
+    submission_id: String,
+    // missing while the verdict is hidden or was never known
+    judgement_type_id: Option<&'static str>,
+    start_time: String,
+    start_contest_time: String,
+    end_time: Option<String>,
+    end_contest_time: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Score {
+    num_solved: usize,
+    total_time: usize,
+}
+
+#[derive(Serialize)]
+struct ScoreboardProblem {
+    problem_id: String,
+    num_judged: usize,
+    num_pending: usize,
+    solved: bool,
+    // contest minute of the accepted submission
+    #[serde(skip_serializing_if = "Option::is_none")]
+    time: Option<usize>,
+    first_to_solve: bool,
+}
+
+#[derive(Serialize)]
+struct ScoreboardRow {
+    rank: usize,
+    team_id: String,
+    score: Score,
+    problems: Vec<ScoreboardProblem>,
+}
+
+#[derive(Serialize)]
+struct ScoreboardResponse {
+    time: String,
+    contest_time: String,
+    state: State,
+    rows: Vec<ScoreboardRow>,
+}
+
+// A submission made up from a team's ProblemStatus, since scraped
+// scoreboards only give the number of tries and the time of the accepted one.
+// Rejected tries are spread evenly before the accepted one,
+// and tries after the freeze evenly over the frozen hour
+struct Attempt {
+    team: usize,
+    problem: usize,
+    seconds: usize,
+    // None while still being judged, or hidden by the freeze
+    correct: Option<bool>,
+}
+
+fn attempts(sb: &Scoreboard) -> Vec<Attempt> {
+    let freeze = FREEZE_MINUTES * 60;
+    let end = CONTEST_MINUTES * 60;
+
+    let mut res = Vec::new();
+    for (team, entry) in sb.entries.iter().enumerate() {
+        for (problem, status) in entry.problems.iter().enumerate() {
+            let attempt = |seconds, correct| Attempt { team, problem, seconds, correct };
+
+            match *status {
+                ProblemStatus::Incorrect => {}
+                ProblemStatus::Pending { attempts } => {
+                    // the last try is the one being judged
+                    let tries = attempts.max(1);
+                    res.extend(spread(tries, 0, end)
+                        .enumerate()
+                        .map(|(i, seconds)| attempt(seconds, if i + 1 < tries { Some(false) } else { None })));
+                }
+                ProblemStatus::Frozen { attempts, pending } => {
+                    res.extend(spread(attempts, 0, freeze).map(|seconds| attempt(seconds, Some(false))));
+                    res.extend(spread(pending, freeze, end).map(|seconds| attempt(seconds, None)));
+                }
+                ProblemStatus::Solved { attempts, seconds, .. } => {
+                    res.extend(spread(attempts.saturating_sub(1), 0, seconds).map(|s| attempt(s, Some(false))));
+                    res.push(attempt(seconds, Some(true)));
+                }
+            }
+        }
+    }
+
+    res.sort_by_key(|a| (a.seconds, a.team, a.problem));
+    res
+}
+
+// "1:23:45.000"
+fn relative(seconds: usize) -> String {
+    format!("{}:{:0>2}:{:0>2}.000", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+fn team_id(team: usize) -> String {
+    (team + 1).to_string()
+}
+
+fn judgement_type(correct: bool) -> &'static str {
+    if correct { "AC" } else { "WA" }
+}
+
+// id of the contest in api paths, e.g. "nac24-standings" for "nac24 standings"
+pub fn contest_id(name: &str) -> String {
+    let id = name.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if id.is_empty() { "contest".to_string() } else { id }
+}
+
+// Serves a Scoreboard as a CLICS contest api under /api,
+// with submissions and judgements appearing as the replay clock reaches them
+pub struct ContestApi<'a> {
+    pub sb: &'a Scoreboard,
+    pub name: &'a str,
+    // wall-clock time at which the replay is at minute 0
+    pub start: SystemTime,
+}
+
+impl ContestApi<'_> {
+    // "2024-09-19T10:00:00.000+00:00"
+    fn absolute(&self, seconds: usize) -> String {
+        DateTime::<Utc>::from(self.start + Duration::from_secs(seconds as u64))
+            .format("%Y-%m-%dT%H:%M:%S%.3f+00:00")
+            .to_string()
+    }
+
+    pub fn id(&self) -> String {
+        contest_id(self.name)
+    }
+
+    fn contest(&self) -> Contest {
+        Contest {
+            id: self.id(),
+            name: self.name.to_string(),
+            formal_name: self.name.to_string(),
+            start_time: self.absolute(0),
+            duration: relative(CONTEST_MINUTES * 60),
+            scoreboard_freeze_duration: relative((CONTEST_MINUTES - FREEZE_MINUTES) * 60),
+            scoreboard_type: "pass-fail",
+            penalty_time: PENALTY_MINUTES,
+        }
+    }
+
+    // None before the replay starts
+    fn state(&self, elapsed: Option<usize>) -> State {
+        let Some(elapsed) = elapsed else {
+            return State::default();
+        };
+        let reached = |seconds: usize| Some(self.absolute(seconds)).filter(|_| elapsed >= seconds);
+        let end = CONTEST_MINUTES * 60;
+        // Nothing is revealed after the contest, so it is final once it ends.
+        // A try whose verdict was never known would stay unjudged in a final contest,
+        // so then it is never finalized
+        let last = reached(end).filter(|_| attempts(self.sb).iter().all(|a| a.correct.is_some()));
+
+        State {
+            started: reached(0),
+            frozen: reached(FREEZE_MINUTES * 60),
+            ended: reached(end),
+            thawed: None,
+            finalized: last.clone(),
+            end_of_updates: last,
+        }
+    }
+
+    fn judgement_types(&self) -> Vec<JudgementType> {
+        vec![
+            JudgementType { id: "AC", name: "correct", penalty: false, solved: true },
+            JudgementType { id: "WA", name: "wrong answer", penalty: true, solved: false },
+        ]
+    }
+
+    fn languages(&self) -> Vec<Language> {
+        vec![Language { id: LANGUAGE_ID, name: "Unknown", entry_point_required: false, extensions: Vec::new() }]
+    }
+
+    fn problems(&self) -> Vec<Problem> {
+        (0..self.sb.num_problems)
+            .map(|i| Problem {
+                id: problem_label(i),
+                label: problem_label(i),
+                name: self.sb.problem_name(i).map_or_else(|| problem_label(i), |n| n.to_string()),
+                ordinal: i,
+            })
+            .collect()
+    }
+
+    // affiliations by id, in order of appearance
+    fn organization_ids(&self) -> HashMap<&str, String> {
+        let mut ids = HashMap::new();
+        for affiliation in self.sb.entries.iter().filter_map(|e| e.affiliation.as_deref()) {
+            let next = (ids.len() + 1).to_string();
+            ids.entry(affiliation).or_insert(next);
+        }
+
+        ids
+    }
+
+    fn organizations(&self) -> Vec<Organization> {
+        let mut organizations = self.organization_ids()
+            .into_iter()
+            .map(|(name, id)| Organization { id, name: name.to_string(), formal_name: name.to_string() })
+            .collect::<Vec<_>>();
+        organizations.sort_by_key(|o| o.id.parse::<usize>().unwrap_or(0));
+
+        organizations
+    }
+
+    fn teams(&self) -> Vec<Team> {
+        let organizations = self.organization_ids();
+
+        self.sb.entries.iter()
+            .enumerate()
+            .map(|(i, e)| Team {
+                id: team_id(i),
+                name: e.team.clone(),
+                organization_id: e.affiliation.as_deref().and_then(|a| organizations.get(a).cloned()),
+            })
+            .collect()
+    }
+
+    fn submission(&self, id: usize, attempt: &Attempt) -> Submission {
+        Submission {
+            id: (id + 1).to_string(),
+            language_id: LANGUAGE_ID,
+            problem_id: problem_label(attempt.problem),
+            team_id: team_id(attempt.team),
+            time: self.absolute(attempt.seconds),
+            contest_time: relative(attempt.seconds),
+            files: Vec::new(),
+        }
+    }
+
+    // judged the moment it was submitted
+    fn judgement(&self, id: usize, attempt: &Attempt) -> Judgement {
+        let judged = attempt.correct.map(|_| attempt.seconds);
+
+        Judgement {
+            id: (id + 1).to_string(),
+            submission_id: (id + 1).to_string(),
+            judgement_type_id: attempt.correct.map(judgement_type),
+            start_time: self.absolute(attempt.seconds),
+            start_contest_time: relative(attempt.seconds),
+            end_time: judged.map(|s| self.absolute(s)),
+            end_contest_time: judged.map(relative),
+        }
+    }
+
+    // elapsed seconds of the replay, None before it starts
+    fn elapsed(&self, now: SystemTime) -> Option<usize> {
+        match replay_clock(self.start, now) {
+            (elapsed, 0) => Some(elapsed),
+            _ => None
+        }
+    }
+
+    fn scoreboard(&self, elapsed: Option<usize>) -> ScoreboardResponse {
+        let seconds = elapsed.unwrap_or(0);
+        let attempts = attempts(self.sb);
+
+        let rows = self.sb.ranking(seconds)
+            .into_iter()
+            .enumerate()
+            .map(|(rank, team)| {
+                let entry = &self.sb.entries[team];
+                let (solved, total_time) = entry.score(seconds);
+
+                let problems = (0..self.sb.num_problems)
+                    .map(|problem| {
+                        let visible = attempts.iter()
+                            .filter(|a| a.team == team && a.problem == problem && elapsed.is_some_and(|e| a.seconds <= e))
+                            .collect::<Vec<_>>();
+                        let accepted = visible.iter().find(|a| a.correct == Some(true));
+                        let first_solve = matches!(entry.problems.get(problem), Some(ProblemStatus::Solved { first_solve: true, .. }));
+
+                        ScoreboardProblem {
+                            problem_id: problem_label(problem),
+                            num_judged: visible.iter().filter(|a| a.correct.is_some()).count(),
+                            num_pending: visible.iter().filter(|a| a.correct.is_none()).count(),
+                            solved: accepted.is_some(),
+                            time: accepted.map(|a| a.seconds / 60),
+                            first_to_solve: accepted.is_some() && first_solve,
+                        }
+                    })
+                    .collect();
+
+                ScoreboardRow {
+                    rank: rank + 1,
+                    team_id: team_id(team),
+                    score: Score { num_solved: (-solved) as usize, total_time },
+                    problems,
+                }
+            })
+            .collect();
+
+        ScoreboardResponse {
+            time: self.absolute(seconds),
+            contest_time: relative(seconds),
+            state: self.state(elapsed),
+            rows,
+        }
+    }
+
+    // Every event of the contest in order, those at or before elapsed first.
+    // Each is (contest seconds it happens at, event),
+    // the contest description comes before the start
+    fn timeline(&self) -> Vec<(Option<usize>, Value)> {
+        let event = |kind: &str, id: Option<&str>, data: Value| json!({ "type": kind, "id": id, "data": data });
+        let collection = |kind: &str, data: Value| (None, event(kind, None, data));
+
+        let mut timed = vec![(0, 0, event("state", None, json!(self.state(Some(0)))))];
+        for (i, attempt) in attempts(self.sb).iter().enumerate() {
+            let submission = self.submission(i, attempt);
+            let judgement = self.judgement(i, attempt);
+            timed.push((attempt.seconds, 1, event("submissions", Some(&submission.id), json!(submission))));
+            timed.push((attempt.seconds, 1, event("judgements", Some(&judgement.id), json!(judgement))));
+        }
+        let freeze = FREEZE_MINUTES * 60;
+        let end = CONTEST_MINUTES * 60;
+        timed.push((freeze, 0, event("state", None, json!(self.state(Some(freeze))))));
+        timed.push((end, 2, event("state", None, json!(self.state(Some(end))))));
+        // stable, so each submission stays before its judgement
+        timed.sort_by_key(|(seconds, order, _)| (*seconds, *order));
+
+        let mut res = vec![
+            (None, event("contest", None, json!(self.contest()))),
+            collection("judgement-types", json!(self.judgement_types())),
+            collection("languages", json!(self.languages())),
+            collection("problems", json!(self.problems())),
+            collection("organizations", json!(self.organizations())),
+            collection("teams", json!(self.teams())),
+            (None, event("state", None, json!(self.state(None)))),
+        ];
+        res.extend(timed.into_iter().map(|(seconds, _, e)| (Some(seconds), e)));
+
+        res
+    }
+
+    // events up to now, in the order of the event feed, each with its token
+    pub fn events(&self, now: SystemTime) -> Vec<Value> {
+        let elapsed = self.elapsed(now);
+
+        self.timeline()
+            .into_iter()
+            .take_while(|(seconds, _)| match (seconds, elapsed) {
+                (None, _) => true,
+                (Some(seconds), Some(elapsed)) => *seconds <= elapsed,
+                (Some(_), None) => false,
+            })
+            .enumerate()
+            .map(|(i, (_, mut e))| {
+                e["token"] = json!(i.to_string());
+                e
+            })
+            .collect()
+    }
+
+    // whether the event feed has nothing more to come,
+    // also when unknown verdicts keep the contest from being finalized
+    pub fn finished(&self, now: SystemTime) -> bool {
+        self.elapsed(now).is_some_and(|e| e >= CONTEST_MINUTES * 60)
+    }
+
+    pub fn event_feed_path(&self) -> String {
+        format!("/api/contests/{}/event-feed", self.id())
+    }
+
+    // json for an api path such as /api/contests/nac24-standings/teams/3,
+    // None if there is nothing at the path
+    pub fn get(&self, path: &str, now: SystemTime) -> Option<Value> {
+        let path = path.trim_end_matches('/').strip_prefix("/api")?;
+        let elapsed = self.elapsed(now);
+
+        match path {
+            "" => return Some(json!({
+                "version": API_VERSION,
+                "version_url": format!("https://ccs-specs.icpc.io/{}/contest_api", API_VERSION),
+            })),
+            "/contests" => return Some(json!([self.contest()])),
+            _ => {}
+        }
+
+        let mut parts = path.strip_prefix("/contests/")?.split('/');
+        if parts.next() != Some(self.id().as_str()) {
+            return None;
+        }
+        let (endpoint, id) = (parts.next(), parts.next());
+        if parts.next().is_some() {
+            return None;
+        }
+
+        let visible = |a: &&Attempt| elapsed.is_some_and(|e| a.seconds <= e);
+        let all = match endpoint {
+            None => return Some(json!(self.contest())),
+            Some("state") => return Some(json!(self.state(elapsed))),
+            Some("scoreboard") => return Some(json!(self.scoreboard(elapsed))),
+            Some("judgement-types") => json!(self.judgement_types()),
+            Some("languages") => json!(self.languages()),
+            Some("problems") => json!(self.problems()),
+            Some("organizations") => json!(self.organizations()),
+            Some("teams") => json!(self.teams()),
+            Some("submissions") => json!(attempts(self.sb).iter().enumerate()
+                .filter(|(_, a)| visible(a))
+                .map(|(i, a)| self.submission(i, a))
+                .collect::<Vec<_>>()),
+            Some("judgements") => json!(attempts(self.sb).iter().enumerate()
+                .filter(|(_, a)| visible(a))
+                .map(|(i, a)| self.judgement(i, a))
+                .collect::<Vec<_>>()),
+            Some(_) => return None,
+        };
+
+        match id {
+            None => Some(all),
+            Some(id) => all.as_array()?
+                .iter()
+                .find(|o| o["id"] == id)
+                .cloned()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use serde_json::json;
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+    use crate::test_util::first_solve;
+    use super::{contest_id, ContestApi};
+
+    fn scoreboard() -> Scoreboard {
+        let team = |name: &str, affiliation: Option<&str>, problems| TeamResult {
+            affiliation: affiliation.map(|a| a.to_string()),
+            ..TeamResult::new(name.to_string(), problems)
+        };
+
+        Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: vec!["Apples".to_string()],
+            entries: vec![
+                team("One", Some("University"), vec![
+                    first_solve(3, 60 * 60),
+                    ProblemStatus::Frozen { attempts: 1, pending: 1 },
+                ]),
+                team("Two", None, vec![
+                    ProblemStatus::Incorrect,
+                    first_solve(1, 30 * 60),
+                ]),
+            ],
+            awards: AwardRules::default(),
+        }
+    }
+
+    fn at(api: &ContestApi, minutes: u64) -> std::time::SystemTime {
+        api.start + Duration::from_secs(minutes * 60)
+    }
+
+    #[test]
+    fn collections() {
+        let sb = scoreboard();
+        let api = ContestApi { sb: &sb, name: "Replay", start: UNIX_EPOCH + Duration::from_secs(1_700_000_000) };
+        let now = at(&api, 0);
+
+        assert_eq!(api.get("/api", now).unwrap()["version"], "2023-06");
+        assert_eq!(api.get("/api/contests/replay", now).unwrap()["start_time"], "2023-11-14T22:13:20.000+00:00");
+        assert_eq!(api.get("/api/contests/replay/problems", now).unwrap(), json!([
+            { "id": "A", "label": "A", "name": "Apples", "ordinal": 0 },
+            { "id": "B", "label": "B", "name": "B", "ordinal": 1 },
+        ]));
+        assert_eq!(api.get("/api/contests/replay/teams/1", now).unwrap(), json!({ "id": "1", "name": "One", "organization_id": "1" }));
+        assert_eq!(api.get("/api/contests/replay/organizations/1", now).unwrap()["name"], "University");
+        assert_eq!(api.get("/api/contests/replay/teams/3", now), None);
+        assert_eq!(api.get("/api/contests/other/teams", now), None);
+        assert_eq!(api.get("/api/contests/replay-2/teams", now), None);
+        assert_eq!(api.get("/api/contests/replay/awards", now), None);
+    }
+
+    #[test]
+    fn submissions_follow_the_clock() {
+        let sb = scoreboard();
+        let api = ContestApi { sb: &sb, name: "Replay", start: UNIX_EPOCH };
+
+        let before = api.start - Duration::from_secs(60);
+        assert_eq!(api.get("/api/contests/replay/submissions", before).unwrap(), json!([]));
+        assert_eq!(api.get("/api/contests/replay/state", before).unwrap()["started"], json!(null));
+
+        // two rejected tries at 0:20 and 0:40, the accepted one at 1:00
+        let judgements = api.get("/api/contests/replay/judgements", at(&api, 45)).unwrap();
+        let verdicts = judgements.as_array().unwrap().iter()
+            .map(|j| (j["start_contest_time"].as_str().unwrap(), j["judgement_type_id"].as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(verdicts, vec![
+            ("0:20:00.000", Some("WA")),
+            ("0:30:00.000", Some("AC")),
+            ("0:40:00.000", Some("WA")),
+        ]);
+
+        // the try after the freeze never gets a verdict
+        let end = at(&api, 300);
+        let judgements = api.get("/api/contests/replay/judgements", end).unwrap();
+        let frozen = judgements.as_array().unwrap().last().unwrap();
+        assert_eq!(frozen["start_contest_time"], "4:30:00.000");
+        assert_eq!(frozen["judgement_type_id"], json!(null));
+        let state = api.get("/api/contests/replay/state", end).unwrap();
+        assert_eq!(state["ended"], "1970-01-01T05:00:00.000+00:00");
+        // so the contest is never final
+        assert_eq!(state["finalized"], json!(null));
+        assert_eq!(state["end_of_updates"], json!(null));
+    }
+
+    #[test]
+    fn finalized_once_every_verdict_is_known() {
+        let mut sb = scoreboard();
+        sb.entries[0].problems[1] = ProblemStatus::Incorrect;
+        let api = ContestApi { sb: &sb, name: "Replay", start: UNIX_EPOCH };
+
+        let state = api.get("/api/contests/replay/state", at(&api, 300)).unwrap();
+        assert_eq!(state["finalized"], "1970-01-01T05:00:00.000+00:00");
+        assert_eq!(state["end_of_updates"], "1970-01-01T05:00:00.000+00:00");
+        assert_eq!(api.get("/api/contests/replay/state", at(&api, 299)).unwrap()["finalized"], json!(null));
+    }
+
+    #[test]
+    fn ids() {
+        assert_eq!(contest_id("nac24 standings"), "nac24-standings");
+        assert_eq!(contest_id("ICPC World Finals (2024)"), "icpc-world-finals-2024");
+        assert_eq!(contest_id("  "), "contest");
+    }
+
+    #[test]
+    fn scoreboard_follows_the_clock() {
+        let sb = scoreboard();
+        let api = ContestApi { sb: &sb, name: "Replay", start: UNIX_EPOCH };
+
+        let board = api.get("/api/contests/replay/scoreboard", at(&api, 45)).unwrap();
+        assert_eq!(board["contest_time"], "0:45:00.000");
+        assert_eq!(board["rows"][0]["team_id"], "2");
+        assert_eq!(board["rows"][0]["score"], json!({ "num_solved": 1, "total_time": 30 }));
+        assert_eq!(board["rows"][1]["problems"][0], json!({
+            "problem_id": "A", "num_judged": 2, "num_pending": 0, "solved": false, "first_to_solve": false,
+        }));
+
+        let board = api.get("/api/contests/replay/scoreboard", at(&api, 300)).unwrap();
+        assert_eq!(board["rows"][0]["team_id"], "2");
+        assert_eq!(board["rows"][1]["score"], json!({ "num_solved": 1, "total_time": 100 }));
+        assert_eq!(board["rows"][1]["problems"][0]["time"], 60);
+        assert_eq!(board["rows"][1]["problems"][0]["first_to_solve"], true);
+        assert_eq!(board["rows"][1]["problems"][1]["num_pending"], 1);
+    }
+
+    #[test]
+    fn event_feed() {
+        let sb = scoreboard();
+        let api = ContestApi { sb: &sb, name: "Replay", start: UNIX_EPOCH };
+
+        let kinds = |minutes| api.events(at(&api, minutes)).iter()
+            .map(|e| e["type"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>();
+
+        let setup = ["contest", "judgement-types", "languages", "problems", "organizations", "teams", "state"];
+        assert_eq!(api.events(api.start - Duration::from_secs(1)).len(), setup.len());
+        assert_eq!(kinds(25)[..setup.len()], setup);
+        assert_eq!(kinds(25)[setup.len()..], ["state", "submissions", "judgements"]);
+
+        let all = api.events(at(&api, 300));
+        // 6 submissions with their judgements and 3 state changes
+        assert_eq!(all.len(), setup.len() + 12 + 3);
+        assert_eq!(all.last().unwrap()["data"]["ended"], "1970-01-01T05:00:00.000+00:00");
+        assert!(all.iter().enumerate().all(|(i, e)| e["token"].as_str().and_then(|t| t.parse().ok()) == Some(i)));
+        // later events only ever get appended
+        assert_eq!(api.events(at(&api, 100))[..], all[..api.events(at(&api, 100)).len()]);
+        assert!(api.finished(at(&api, 300)));
+        assert!(!api.finished(at(&api, 299)));
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::{HashMap, VecDeque};
+use crate::scoreboard::Scoreboard;
+use crate::stats::problem_stats;
+
+// a scoreboard as it stood at a given time
+#[derive(Clone, Copy)]
+pub struct Snapshot<'a> {
+    pub sb: &'a Scoreboard,
+    pub elapsed_seconds: usize,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TeamChange {
+    pub team: String,
+    // 1-indexed ranks, None if the team is missing from that snapshot
+    pub before: Option<usize>,
+    pub after: Option<usize>,
+    // problems solved in the second snapshot but not in the first
+    pub newly_solved: Vec<usize>,
+}
+
+impl TeamChange {
+    // positive when the team moved up
+    pub fn rank_delta(&self) -> Option<isize> {
+        Some(self.before? as isize - self.after? as isize)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProblemChange {
+    pub problem: usize,
+    // fraction of teams that solved the problem, None if the snapshot has no such problem
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Comparison {
+    // in order of the second snapshot, followed by teams only present in the first
+    pub teams: Vec<TeamChange>,
+    pub problems: Vec<ProblemChange>,
+}
+
+fn solve_rates(snapshot: Snapshot) -> Vec<f64> {
+    let teams = snapshot.sb.entries.len().max(1) as f64;
+
+    problem_stats(snapshot.sb, snapshot.elapsed_seconds)
+        .into_iter()
+        .map(|stats| stats.solved as f64 / teams)
+        .collect()
+}
+
+// Teams are matched by name and institution, problems by position,
+// so two different contests can be compared as well as two minutes of one.
+// Teams sharing both are paired up in rank order rather than merged
+pub fn compare(before: Snapshot, after: Snapshot) -> Comparison {
+    let mut before_ranks: HashMap<(&str, &str), VecDeque<(usize, usize)>> = HashMap::new();
+    for (rank, team) in before.sb.ranking(before.elapsed_seconds).into_iter().enumerate() {
+        let entry = &before.sb.entries[team];
+        before_ranks.entry((entry.team.as_str(), entry.institution()))
+            .or_default()
+            .push_back((rank + 1, team));
+    }
+
+    let mut teams = after.sb.ranking(after.elapsed_seconds)
+        .into_iter()
+        .enumerate()
+        .map(|(rank, team)| {
+            let entry = &after.sb.entries[team];
+            let previous = before_ranks.get_mut(&(entry.team.as_str(), entry.institution()))
+                .and_then(|namesakes| namesakes.pop_front());
+
+            let newly_solved = entry.problems.iter()
+                .enumerate()
+                .filter(|(_, status)| status.penalty(after.elapsed_seconds).is_some())
+                .filter(|(i, _)| {
+                    previous
+                        .and_then(|(_, prev)| before.sb.entries[prev].problems.get(*i))
+                        .is_none_or(|status| status.penalty(before.elapsed_seconds).is_none())
+                })
+                .map(|(i, _)| i)
+                .collect();
+
+            TeamChange {
+                team: entry.team.clone(),
+                before: previous.map(|(rank, _)| rank),
+                after: Some(rank + 1),
+                newly_solved,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // whatever was not paired up is gone from the later snapshot
+    let mut missing = before_ranks.into_iter()
+        .flat_map(|((name, _), namesakes)| namesakes.into_iter().map(move |(rank, _)| TeamChange {
+            team: name.to_string(),
+            before: Some(rank),
+            after: None,
+            newly_solved: Vec::new(),
+        }))
+        .collect::<Vec<_>>();
+    missing.sort_by_key(|t| t.before);
+    teams.extend(missing);
+
+    let before_rates = solve_rates(before);
+    let after_rates = solve_rates(after);
+    let problems = (0..before_rates.len().max(after_rates.len()))
+        .map(|problem| ProblemChange {
+            problem,
+            before: before_rates.get(problem).copied(),
+            after: after_rates.get(problem).copied(),
+        })
+        .collect();
+
+    Comparison {
+        teams,
+        problems,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoreboard::{AwardRules, Scoreboard, TeamResult};
+    use crate::test_util::{solved, team};
+    use super::{compare, Snapshot, TeamChange};
+
+    fn scoreboard(entries: Vec<TeamResult>) -> Scoreboard {
+        Scoreboard { name: String::new(), num_problems: 2, problem_names: Vec::new(), entries, awards: AwardRules::default() }
+    }
+
+    fn change(team: &str, before: Option<usize>, after: Option<usize>, newly_solved: Vec<usize>) -> TeamChange {
+        TeamChange { team: team.to_string(), before, after, newly_solved }
+    }
+
+    #[test]
+    fn ranks_and_solves() {
+        let sb = scoreboard(vec![
+            team("Early", vec![solved(1, 10 * 60)]),
+            team("Late", vec![solved(1, 20 * 60), solved(1, 100 * 60)]),
+        ]);
+        let at = |minutes: usize| Snapshot { sb: &sb, elapsed_seconds: minutes * 60 };
+
+        let comparison = compare(at(60), at(120));
+        assert_eq!(comparison.teams, vec![
+            change("Late", Some(2), Some(1), vec![1]),
+            change("Early", Some(1), Some(2), Vec::new()),
+        ]);
+        assert_eq!(comparison.teams[0].rank_delta(), Some(1));
+        assert_eq!(comparison.problems[1].before, Some(0.0));
+        assert_eq!(comparison.problems[1].after, Some(0.5));
+    }
+
+    #[test]
+    fn namesakes() {
+        let at = |name: &str, school: &str, problems| TeamResult {
+            affiliation: Some(school.to_string()),
+            ..team(name, problems)
+        };
+        let before = scoreboard(vec![
+            at("Team 1", "MIT", vec![solved(1, 10 * 60)]),
+            at("Team 1", "ETH", Vec::new()),
+            team("Guests", vec![solved(1, 20 * 60)]),
+            team("Guests", Vec::new()),
+        ]);
+        let after = scoreboard(vec![
+            at("Team 1", "ETH", vec![solved(1, 5 * 60), solved(1, 6 * 60)]),
+            team("Guests", vec![solved(1, 30 * 60)]),
+            at("Team 1", "MIT", vec![solved(1, 10 * 60)]),
+        ]);
+
+        let comparison = compare(
+            Snapshot { sb: &before, elapsed_seconds: 3600 },
+            Snapshot { sb: &after, elapsed_seconds: 3600 },
+        );
+        assert_eq!(comparison.teams, vec![
+            // matched by institution, not by the first team of that name
+            change("Team 1", Some(3), Some(1), vec![0, 1]),
+            change("Team 1", Some(1), Some(2), Vec::new()),
+            // the better placed namesake is paired first, the other one left over
+            change("Guests", Some(2), Some(3), Vec::new()),
+            change("Guests", Some(4), None, Vec::new()),
+        ]);
+    }
+}
@@ -0,0 +1,99 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+use serde_json::Value;
+use crate::clics::ContestApi;
+use crate::scoreboard::{Scoreboard, CONTEST_MINUTES};
+
+// one event per line, as in a CLICS event-feed.ndjson
+pub fn ndjson(events: &[Value]) -> String {
+    events.iter()
+        .map(|e| e.to_string() + "\n")
+        .collect()
+}
+
+// The event feed of the whole contest held from start, through to the end of the contest.
+// Submissions and judgements are made up from the scoreboard as for the contest api
+pub fn event_feed(sb: &Scoreboard, name: &str, start: SystemTime) -> String {
+    let api = ContestApi { sb, name, start };
+
+    ndjson(&api.events(start + Duration::from_secs(CONTEST_MINUTES as u64 * 60)))
+}
+
+pub fn write_event_feed(sb: &Scoreboard, name: &str, start: SystemTime, path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, event_feed(sb, name, start))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use serde_json::Value;
+    use crate::parser::pc2::parse_results_xml;
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard};
+    use crate::test_util::{first_solve, team};
+    use super::event_feed;
+
+    #[test]
+    fn whole_contest() {
+        let sb = Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: Vec::new(),
+            entries: vec![
+                team("One", vec![
+                    first_solve(2, 600),
+                    ProblemStatus::Pending { attempts: 1 },
+                ]),
+                team("Two", vec![ProblemStatus::Incorrect, ProblemStatus::Incorrect]),
+            ],
+            awards: AwardRules::default(),
+        };
+        let feed = event_feed(&sb, "Test Contest 2024", UNIX_EPOCH + Duration::from_secs(1_726_740_000));
+        let events = feed.lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        let kinds = events.iter()
+            .map(|e| e["type"].as_str().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, [
+            "contest", "judgement-types", "languages", "problems", "organizations", "teams", "state",
+            // started
+            "state",
+            // rejected, then accepted at 0:10
+            "submissions", "judgements", "submissions", "judgements",
+            // still being judged
+            "submissions", "judgements",
+            // frozen, then ended
+            "state", "state",
+        ]);
+
+        assert_eq!(events[0]["data"]["id"], "test-contest-2024");
+        assert_eq!(events[0]["data"]["name"], "Test Contest 2024");
+        assert_eq!(events[0]["data"]["start_time"], "2024-09-19T10:00:00.000+00:00");
+        assert_eq!(events[8]["data"]["contest_time"], "0:05:00.000");
+        assert_eq!(events[11]["data"]["judgement_type_id"], "AC");
+        assert_eq!(events[13]["data"]["judgement_type_id"], Value::Null);
+        assert_eq!(events[15]["data"]["ended"], "2024-09-19T15:00:00.000+00:00");
+        // the pending try is never judged, so the contest is not final
+        assert_eq!(events[15]["data"]["end_of_updates"], Value::Null);
+    }
+
+    #[test]
+    fn parsed_contest() {
+        let sb = parse_results_xml(include_str!("../tests/fixtures/pc2_results.xml")).unwrap();
+
+        let feed = event_feed(&sb, "Replay", UNIX_EPOCH);
+        let submissions = feed.lines()
+            .filter(|line| line.contains("\"type\":\"submissions\""))
+            .count();
+
+        // unsolved problems have no known tries
+        assert_eq!(submissions, (1 + 3) + (2 + 1));
+    }
+}
@@ -0,0 +1,208 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use quarve::resource::local_storage;
+use quarve::state::{SetAction, Stateful};
+use quarve::util::marker::FalseMarker;
+
+const MAX_RECENT: usize = 10;
+
+// a contest that can be reopened from the start screen
+#[derive(Clone, PartialEq, Debug)]
+pub struct ContestEntry {
+    pub contest_type: String,
+    pub url: String,
+    pub name: String,
+    // unix seconds at which it was loaded or bookmarked
+    pub date: u64,
+}
+
+impl ContestEntry {
+    // name is the contest's title, made up from the url if the source gives none
+    pub fn new(contest_type: &str, url: &str, name: &str, now: SystemTime) -> ContestEntry {
+        ContestEntry {
+            contest_type: contest_type.to_string(),
+            url: url.to_string(),
+            // tabs separate the fields of the saved entry
+            name: if name.trim().is_empty() { name_from_url(url) } else { name.trim().replace('\t', " ") },
+            date: now.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()),
+        }
+    }
+
+    fn same_contest(&self, other: &ContestEntry) -> bool {
+        self.contest_type == other.contest_type && self.url == other.url
+    }
+}
+
+// e.g. "https://open.kattis.com/contests/nac24/standings" becomes "nac24 standings"
+pub fn name_from_url(url: &str) -> String {
+    let parts = url.trim_end_matches('/')
+        .rsplit('/')
+        .filter(|p| !p.is_empty() && !p.contains(':'))
+        .take(2)
+        .collect::<Vec<_>>();
+
+    if parts.is_empty() {
+        url.to_string()
+    } else {
+        parts.into_iter().rev().collect::<Vec<_>>().join(" ")
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct History {
+    // most recent first
+    pub recent: Vec<ContestEntry>,
+    pub bookmarks: Vec<ContestEntry>,
+}
+
+impl Stateful for History {
+    type Action = SetAction<History>;
+    type HasInnerStores = FalseMarker;
+}
+
+fn recent_path() -> PathBuf {
+    local_storage().join("recent.txt")
+}
+
+fn bookmarks_path() -> PathBuf {
+    local_storage().join("bookmarks.txt")
+}
+
+// one tab separated entry per line
+fn read_entries(path: PathBuf) -> Vec<ContestEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+
+    content.lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some(ContestEntry {
+                contest_type: fields.next()?.to_string(),
+                url: fields.next()?.to_string(),
+                name: fields.next()?.to_string(),
+                date: fields.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+fn write_entries(path: PathBuf, entries: &[ContestEntry]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = entries.iter()
+        .map(|e| format!("{}\t{}\t{}\t{}\n", e.contest_type, e.url, e.name, e.date))
+        .collect::<String>();
+    fs::write(path, content)
+}
+
+impl History {
+    pub fn load() -> History {
+        History {
+            recent: read_entries(recent_path()),
+            bookmarks: read_entries(bookmarks_path()),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        write_entries(recent_path(), &self.recent)?;
+        write_entries(bookmarks_path(), &self.bookmarks)
+    }
+
+    // moves the contest to the front of the recent list
+    pub fn record(&mut self, entry: ContestEntry) {
+        self.recent.retain(|e| !e.same_contest(&entry));
+        self.recent.insert(0, entry);
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    pub fn is_bookmarked(&self, entry: &ContestEntry) -> bool {
+        self.bookmarks.iter().any(|e| e.same_contest(entry))
+    }
+
+    pub fn toggle_bookmark(&mut self, entry: &ContestEntry) {
+        if self.is_bookmarked(entry) {
+            self.bookmarks.retain(|e| !e.same_contest(entry));
+        } else {
+            self.bookmarks.push(entry.clone());
+        }
+    }
+}
+
+// called once a contest has been loaded successfully, with the name it was parsed with
+pub fn record_recent(contest_type: &str, url: &str, name: &str) -> io::Result<()> {
+    let mut history = History::load();
+    history.record(ContestEntry::new(contest_type, url, name, SystemTime::now()));
+    history.save()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use super::{name_from_url, ContestEntry, History, MAX_RECENT};
+
+    fn entry(url: &str) -> ContestEntry {
+        ContestEntry::new("KATTIS", url, "", UNIX_EPOCH + Duration::from_secs(60))
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(name_from_url("https://open.kattis.com/contests/nac24/standings"), "nac24 standings");
+        assert_eq!(name_from_url("https://codeforces.com/contest/2052/"), "contest 2052");
+        assert_eq!(name_from_url(""), "");
+
+        // the parsed title wins over the url
+        assert_eq!(ContestEntry::new("KATTIS", "https://x/nac24", " NAC 2024 ", UNIX_EPOCH).name, "NAC 2024");
+        assert_eq!(entry("https://x/nac24").name, "x nac24");
+        assert_eq!(entry("https://x/nac24").date, 60);
+    }
+
+    #[test]
+    fn record() {
+        let mut history = History::default();
+        history.record(entry("a"));
+        history.record(entry("b"));
+        history.record(entry("a"));
+
+        // reopening moves a contest to the front instead of listing it twice
+        assert_eq!(history.recent, vec![entry("a"), entry("b")]);
+
+        let mut other_type = entry("b");
+        other_type.contest_type = "CERC".to_string();
+        history.record(other_type.clone());
+        assert_eq!(history.recent, vec![other_type, entry("a"), entry("b")]);
+    }
+
+    #[test]
+    fn truncation() {
+        let mut history = History::default();
+        for i in 0..MAX_RECENT + 2 {
+            history.record(entry(&i.to_string()));
+        }
+
+        assert_eq!(history.recent.len(), MAX_RECENT);
+        assert_eq!(history.recent[0], entry(&(MAX_RECENT + 1).to_string()));
+        // the oldest ones are dropped
+        assert_eq!(history.recent[MAX_RECENT - 1], entry("2"));
+    }
+
+    #[test]
+    fn bookmarks() {
+        let mut history = History::default();
+        history.toggle_bookmark(&entry("a"));
+        history.toggle_bookmark(&entry("b"));
+        assert!(history.is_bookmarked(&entry("a")));
+
+        // matched by contest, not by name or date
+        let mut renamed = entry("a");
+        renamed.name = "Renamed".to_string();
+        renamed.date = 0;
+        history.toggle_bookmark(&renamed);
+        assert!(!history.is_bookmarked(&entry("a")));
+        assert_eq!(history.bookmarks, vec![entry("b")]);
+    }
+}
@@ -0,0 +1,13 @@
+// contest model, parsers and scoring shared by the app and other tools,
+// free of any ui dependency
+pub mod scoreboard;
+pub mod parser;
+pub mod stats;
+pub mod compare;
+pub mod resolver;
+pub mod replay;
+pub mod server;
+pub mod clics;
+pub mod export;
+#[cfg(test)]
+mod test_util;
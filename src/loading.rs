@@ -0,0 +1,72 @@
+use std::error::Error;
+use quarve::core::slock_owner;
+use quarve::state::{Binding, Filterless, SetAction, Stateful};
+use quarve::state::SetAction::Set;
+use quarve::util::marker::FalseMarker;
+use quarve::view::modal::{MessageBox, MessageBoxButton};
+use icpc_scoreboard::parser::parse;
+use icpc_scoreboard::scoreboard::Scoreboard;
+use crate::history::record_recent;
+
+// need a new typ
+#[derive(Clone, PartialEq)]
+pub enum ScoreboardOption {
+    Some(Scoreboard),
+    // still being fetched, with a progress message
+    Loading(String),
+    None
+}
+
+impl Stateful for ScoreboardOption {
+    type Action = SetAction<ScoreboardOption>;
+    type HasInnerStores = FalseMarker;
+}
+
+pub fn handle(f: Result<Scoreboard, Box<dyn Error>>, result: impl Binding<Filterless<ScoreboardOption>>) {
+    match f {
+        Ok(scoreboard) => {
+            let s = slock_owner();
+            result.apply(Set(ScoreboardOption::Some(scoreboard)), s.marker());
+        }
+        Err(e) => {
+            {
+                let s = slock_owner();
+                result.apply(Set(ScoreboardOption::None), s.marker());
+            }
+
+            MessageBox::new(
+                Some("Operation Failed"),
+                Some(&e.to_string())
+            )
+                .button(MessageBoxButton::Ok)
+                .run(|_, _| {})
+        }
+    }
+}
+
+// loads a contest to show and adds it to the recent contests
+pub async fn begin_parse(contest_type: &str, url: &str, result: impl Binding<Filterless<ScoreboardOption>>) {
+    load(contest_type, url, result, true).await
+}
+
+// loads a contest without adding it to the recent contests, e.g. one to compare against
+pub async fn begin_load(contest_type: &str, url: &str, result: impl Binding<Filterless<ScoreboardOption>>) {
+    load(contest_type, url, result, false).await
+}
+
+async fn load(contest_type: &str, url: &str, result: impl Binding<Filterless<ScoreboardOption>>, record: bool) {
+    let progress = |status: String| {
+        let s = slock_owner();
+        result.apply(Set(ScoreboardOption::Loading(status)), s.marker());
+    };
+    progress("Loading...".to_string());
+
+    let parsed = parse(contest_type, url, &progress).await;
+
+    // failing to remember the contest should not prevent showing it
+    if let (true, Ok(sb)) = (record, &parsed) {
+        let _ = record_recent(contest_type, url, &sb.name);
+    }
+
+    handle(parsed, result);
+}
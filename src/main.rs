@@ -1,12 +1,45 @@
-mod scoreboard;
-mod parser;
+mod session;
+mod history;
+mod catalog;
+mod loading;
+mod cli;
 mod views;
 
+use std::time::SystemTime;
+use quarve::core::{slock_owner, with_app};
+use quarve::event::EventModifiers;
 use quarve::prelude::*;
+use icpc_scoreboard::parser::CONTEST_TYPES;
+use crate::session::{Session, WindowSession};
 use crate::views::viewer;
 
 struct App;
-struct MainWindow;
+// each window replays its own contest with its own timer
+struct MainWindow {
+    // running session restored in this window on launch
+    restored: Option<Session>,
+    session: Store<WindowSession>,
+}
+
+impl MainWindow {
+    fn new(restored: Option<Session>) -> MainWindow {
+        let session = Store::new(WindowSession(restored.clone()));
+        MainWindow { restored, session }
+    }
+}
+
+// the window is dropped once it is closed, and its replay should not come back on the next launch
+impl Drop for MainWindow {
+    fn drop(&mut self) {
+        let s = slock_owner();
+        let session = self.session.borrow(s.marker()).0.clone();
+        if let Some(session) = session {
+            if let Err(e) = Session::remove(&session.url) {
+                eprintln!("Unable to remove session: {}", e);
+            }
+        }
+    }
+}
 pub(crate) struct Env(StandardConstEnv, StandardVarEnv);
 
 // mainly boilerplate
@@ -17,8 +50,18 @@ impl ApplicationProvider for App {
     }
 
     fn will_spawn(&self, app: &quarve::core::Application, s: MSlock) {
-        // init code goes here
-        app.spawn_window(MainWindow, s);
+        // one window per session that is still running
+        let sessions = Session::load_active(SystemTime::now())
+            .into_iter()
+            .filter(|session| CONTEST_TYPES.contains(&session.contest_type.as_str()))
+            .collect::<Vec<_>>();
+
+        if sessions.is_empty() {
+            app.spawn_window(MainWindow::new(None), s);
+        }
+        for session in sessions {
+            app.spawn_window(MainWindow::new(Some(session)), s);
+        }
     }
 }
 
@@ -42,14 +85,17 @@ impl WindowProvider for MainWindow {
     }
 
     fn root(&self, env: &<Env as Environment>::Const, s: MSlock) -> impl ViewProvider<Env, DownContext=()> {
-        viewer()
+        viewer(self.restored.clone(), self.session.binding())
             .into_view_provider(env, s)
     }
 
     fn menu(&self, env: &<Self::Environment as Environment>::Const, s: MSlock) -> WindowMenu {
         WindowMenu::standard(
             env,
-            Menu::new("File"),
+            Menu::new("File")
+                .push(MenuButton::new("New Window", "n", EventModifiers::new().set_command(), |s| {
+                    with_app(|app| app.spawn_window(MainWindow::new(None), s), s);
+                })),
             Menu::new("Edit"),
             Menu::new("View"),
             Menu::new("Help"),
@@ -87,6 +133,21 @@ impl<I> IVP for I where I: IntoViewProvider<Env, DownContext=(), UpContext=()> {
 
 #[tokio::main]
 async fn main() {
-    quarve::core::launch(App);
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+
+    let result = match args.first().map(|a| a.as_str()) {
+        Some("serve") => cli::serve(&args[1..]).await,
+        Some("export") => cli::export(&args[1..]).await,
+        Some(_) => Err(cli::USAGE.into()),
+        None => {
+            quarve::core::launch(App);
+            return;
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
 }
 
@@ -1,16 +1,11 @@
-use reqwest;
 use scraper::{Html, Selector};
 use std::error::Error;
-use crate::parser::util::add_first_solves;
-use crate::scoreboard::{ProblemStatus, Scoreboard, TeamResult};
-
-pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn Error>> {
-    // Fetch the HTML content
-    let html_content = reqwest::get(url).await?.text().await?;
-
-    // Parse the HTML
-    let document = Html::parse_document(&html_content);
+use crate::parser::util::{add_first_solves, fetch_pages, Fetched};
+use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
 
+// Rows of a single standings page.
+// Only rows present in the html are read, rows a page loads later by script are not fetched
+fn parse_page(document: &Html) -> Vec<TeamResult> {
     // Selectors for parsing
     let table_selector = Selector::parse("table.scoreboard").unwrap();
     let team_row_selector = Selector::parse("tr[data-ajax-id]").unwrap();
@@ -20,7 +15,6 @@ pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn
     let penalty_time_selector = Selector::parse(".penalty-time").unwrap();
 
     let mut entries = Vec::new();
-    let mut num_problems = 0;
 
     // Locate the scoreboard table
     if let Some(scoreboard_table) = document.select(&table_selector).next() {
@@ -39,54 +33,182 @@ pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn
             for task in team_row.select(&task_selector) {
                 let task_class = task.value().attr("class").unwrap_or("");
 
-                if task_class.contains("solved") {
-                    let attempts = task
-                        .select(&tries_selector)
-                        .next()
-                        .and_then(|el| el.inner_html().trim().parse::<usize>().ok())
-                        .unwrap_or(0);
+                let attempts = task
+                    .select(&tries_selector)
+                    .next()
+                    .and_then(|el| parse_count(&el.text().collect::<String>()))
+                    .unwrap_or(0);
 
-                    let penalty_time = task
+                if task_class.contains("solved") {
+                    // a solved cell took at least one try, even if the count is missing
+                    let attempts = attempts.max(1);
+                    // despite the class name this is the time of the accepted submission,
+                    // a cell with 10 tries at 1:00:00 could not include 3 hours of penalty
+                    let seconds = task
                         .select(&penalty_time_selector)
                         .next()
-                        .and_then(|el| parse_time_to_minutes(&el.inner_html().trim(), attempts))
-                        .unwrap_or(0);
+                        .and_then(|el| parse_clock(el.text().collect::<String>().trim()));
+
+                    // a solve without a readable time can't be placed in the replay,
+                    // so the cell is left out rather than shown at 0:00
+                    let Some(seconds) = seconds else {
+                        problems.push(ProblemStatus::Incorrect);
+                        continue;
+                    };
 
                     let first_solve = false; // Assuming first_solve isn't in the data provided
 
                     problems.push(ProblemStatus::Solved {
                         attempts,
-                        minutes: penalty_time,
+                        seconds,
                         first_solve,
                     });
+                } else if task_class.contains("frozen") {
+                    problems.push(ProblemStatus::Frozen {
+                        attempts: 0,
+                        pending: attempts.max(1),
+                    });
+                } else if task_class.contains("pending") {
+                    problems.push(ProblemStatus::Pending {
+                        attempts,
+                    });
                 } else {
                     problems.push(ProblemStatus::Incorrect);
                 }
             }
 
-            num_problems = num_problems.max(problems.len());
-            entries.push(TeamResult { team: team_name, problems });
+            // the page lists no institution
+            entries.push(TeamResult::new(team_name, problems));
         }
     }
 
+    entries
+}
+
+fn to_scoreboard(entries: Vec<TeamResult>) -> Scoreboard {
+    let num_problems = entries.iter()
+        .map(|e| e.problems.len())
+        .max()
+        .unwrap_or(0);
+
     let mut res = Scoreboard {
+        name: String::new(),
         num_problems,
+        problem_names: Vec::new(),
         entries,
+        awards: AwardRules::default(),
     };
     add_first_solves(&mut res);
 
-    Ok(res)
+    res
+}
+
+pub async fn fetch_and_parse_scoreboard(url: &str, fetched: Option<&Fetched>, progress: &(dyn Fn(String) + Sync)) -> Result<Scoreboard, Box<dyn Error>> {
+    let entries = fetch_pages(url, fetched, progress, parse_page).await?;
+
+    Ok(to_scoreboard(entries))
+}
+
+// leading number of a cell such as "3" or "3 tries"
+fn parse_count(text: &str) -> Option<usize> {
+    let digits = text.trim()
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>();
+
+    digits.parse().ok()
+}
+
+// "H:MM:SS", "MM:SS" or plain minutes
+fn parse_clock(time_str: &str) -> Option<usize> {
+    let parts = time_str.split(':')
+        .map(|p| p.trim().parse::<usize>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    match parts[..] {
+        [minutes] => Some(minutes * 60),
+        [minutes, seconds] => Some(minutes * 60 + seconds),
+        [hours, minutes, seconds] => Some(hours * 3600 + minutes * 60 + seconds),
+        _ => None
+    }
 }
 
-fn parse_time_to_minutes(time_str: &str, attempts: usize) -> Option<usize> {
-    let parts: Vec<&str> = time_str.split(':').collect();
-    if parts.len() == 3 {
-        let hours = parts[0].parse::<usize>().ok()?;
-        let minutes = parts[1].parse::<usize>().ok()?;
-        let seconds = parts[2].parse::<usize>().ok()?;
-        let offset = (attempts - 1) * 20;
-        Some(hours * 60 + minutes + (seconds / 60) - offset)
-    } else {
-        None
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard};
+    use crate::test_util::{first_solve, solved, team};
+    use super::{parse_clock, parse_page, to_scoreboard};
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/cerc.html");
+
+    #[test]
+    fn clock_formats() {
+        assert_eq!(parse_clock("1:02:03"), Some(3723));
+        assert_eq!(parse_clock("52:10"), Some(3130));
+        assert_eq!(parse_clock("52"), Some(3120));
+        assert_eq!(parse_clock(""), None);
+        assert_eq!(parse_clock("1:2:3:4"), None);
+    }
+
+    #[test]
+    fn fixture_page() {
+        let entries = parse_page(&Html::parse_document(FIXTURE));
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].team, "First Try");
+        assert_eq!(entries[0].problems, vec![
+            solved(1, 52 * 60),
+            // the displayed time, whatever the number of tries
+            solved(4, 3 * 3600 + 15 * 60 + 30),
+            ProblemStatus::Incorrect,
+        ]);
+
+        assert_eq!(entries[1].team, "Edge Cases");
+        assert_eq!(entries[1].problems, vec![
+            // missing try count
+            solved(1, 10 * 60),
+            solved(10, 60 * 60),
+            ProblemStatus::Pending { attempts: 2 },
+        ]);
+
+        assert_eq!(entries[2].team, "Frozen");
+        assert_eq!(entries[2].problems, vec![
+            ProblemStatus::Frozen { attempts: 0, pending: 3 },
+            ProblemStatus::Incorrect,
+            // unparseable time
+            ProblemStatus::Incorrect,
+        ]);
+    }
+
+    #[test]
+    fn fixture_scoreboard() {
+        let sb = to_scoreboard(parse_page(&Html::parse_document(FIXTURE)));
+
+        assert_eq!(sb, Scoreboard {
+            name: String::new(),
+            num_problems: 3,
+            problem_names: Vec::new(),
+            entries: vec![
+                team("First Try", vec![
+                    solved(1, 52 * 60),
+                    solved(4, 3 * 3600 + 15 * 60 + 30),
+                    ProblemStatus::Incorrect,
+                ]),
+                // earliest solve of each problem
+                team("Edge Cases", vec![
+                    first_solve(1, 10 * 60),
+                    first_solve(10, 60 * 60),
+                    ProblemStatus::Pending { attempts: 2 },
+                ]),
+                team("Frozen", vec![
+                    ProblemStatus::Frozen { attempts: 0, pending: 3 },
+                    ProblemStatus::Incorrect,
+                    // not a first solve, nor a solve at all
+                    ProblemStatus::Incorrect,
+                ]),
+            ],
+            awards: AwardRules::default(),
+        });
     }
 }
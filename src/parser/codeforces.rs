@@ -0,0 +1,218 @@
+use std::error::Error;
+use serde::Deserialize;
+use crate::parser::util::{add_first_solves, fetch_text_reusing, Fetched};
+use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+
+// which kinds of participants are included in the standings
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Participation {
+    Official,
+    OfficialAndVirtual,
+    // also includes out of competition teams
+    All,
+}
+
+impl Participation {
+    fn includes(self, participant_type: &str) -> bool {
+        match participant_type {
+            "CONTESTANT" => true,
+            "VIRTUAL" => self != Participation::Official,
+            "OUT_OF_COMPETITION" => self == Participation::All,
+            // practice and manager rows are never part of the standings
+            _ => false
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Response {
+    status: String,
+    comment: Option<String>,
+    result: Option<Standings>,
+}
+
+#[derive(Deserialize)]
+struct Standings {
+    contest: Contest,
+    problems: Vec<Problem>,
+    rows: Vec<Row>,
+}
+
+#[derive(Deserialize)]
+struct Contest {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Problem {
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Row {
+    party: Party,
+    problem_results: Vec<ProblemResult>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Party {
+    participant_type: String,
+    team_name: Option<String>,
+    members: Vec<Member>,
+}
+
+#[derive(Deserialize)]
+struct Member {
+    handle: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProblemResult {
+    points: f64,
+    rejected_attempt_count: usize,
+    best_submission_time_seconds: Option<usize>,
+}
+
+// Accepts a saved API response, an API url,
+// or a contest / gym url such as https://codeforces.com/gym/104757
+fn standings_url(url: &str) -> Option<String> {
+    if url.contains("/api/contest.standings") {
+        return Some(url.to_string());
+    }
+
+    let mut parts = url.split('/').skip_while(|p| *p != "contest" && *p != "gym");
+    parts.next()?;
+    let id = parts.next()?.parse::<usize>().ok()?;
+
+    Some(format!("https://codeforces.com/api/contest.standings?contestId={}&showUnofficial=true", id))
+}
+
+pub fn parse_standings(json: &str, participation: Participation) -> Result<Scoreboard, Box<dyn Error>> {
+    let response: Response = serde_json::from_str(json)?;
+    if response.status != "OK" {
+        return Err(response.comment.unwrap_or(response.status).into());
+    }
+    let standings = response.result.ok_or("Missing standings in response")?;
+
+    let entries = standings.rows.into_iter()
+        .filter(|row| participation.includes(&row.party.participant_type))
+        .map(|row| {
+            let team = row.party.team_name
+                .unwrap_or_else(|| {
+                    row.party.members.iter()
+                        .map(|m| m.handle.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                });
+
+            let problems = row.problem_results.iter()
+                .map(|result| match result.best_submission_time_seconds {
+                    Some(seconds) if result.points > 0.0 => ProblemStatus::Solved {
+                        attempts: result.rejected_attempt_count + 1,
+                        seconds,
+                        first_solve: false,
+                    },
+                    _ => ProblemStatus::Incorrect
+                })
+                .collect();
+
+            // the api gives no institution
+            TeamResult::new(team, problems)
+        })
+        .collect();
+
+    let mut res = Scoreboard {
+        name: standings.contest.name,
+        num_problems: standings.problems.len(),
+        problem_names: standings.problems.into_iter()
+            .map(|p| p.name)
+            .collect(),
+        entries,
+        awards: AwardRules::default(),
+    };
+    add_first_solves(&mut res);
+
+    Ok(res)
+}
+
+pub async fn fetch_and_parse_scoreboard(url: &str, fetched: Option<&Fetched>, participation: Participation) -> Result<Scoreboard, Box<dyn Error>> {
+    let url = standings_url(url).unwrap_or_else(|| url.to_string());
+    let json = fetch_text_reusing(&url, fetched).await?;
+
+    parse_standings(&json, participation)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+    use crate::test_util::{first_solve, solved, team};
+    use super::{parse_standings, standings_url, Participation};
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/codeforces.json");
+
+    fn scoreboard(entries: Vec<TeamResult>) -> Scoreboard {
+        Scoreboard {
+            name: "Practice".to_string(),
+            num_problems: 2,
+            problem_names: vec!["Apples".to_string(), "Bananas".to_string()],
+            entries,
+            awards: AwardRules::default(),
+        }
+    }
+
+    #[test]
+    fn urls() {
+        assert_eq!(
+            standings_url("https://codeforces.com/gym/104757/standings").as_deref(),
+            Some("https://codeforces.com/api/contest.standings?contestId=104757&showUnofficial=true")
+        );
+        assert_eq!(
+            standings_url("https://codeforces.com/contest/1912").as_deref(),
+            Some("https://codeforces.com/api/contest.standings?contestId=1912&showUnofficial=true")
+        );
+        assert_eq!(
+            standings_url("https://codeforces.com/api/contest.standings?contestId=1").as_deref(),
+            Some("https://codeforces.com/api/contest.standings?contestId=1")
+        );
+        assert_eq!(standings_url("standings.json"), None);
+    }
+
+    #[test]
+    fn official() {
+        assert_eq!(parse_standings(FIXTURE, Participation::Official).unwrap(), scoreboard(vec![
+            team("Team One", vec![first_solve(1, 600), first_solve(3, 3000)]),
+        ]));
+    }
+
+    #[test]
+    fn virtual_participants() {
+        assert_eq!(parse_standings(FIXTURE, Participation::OfficialAndVirtual).unwrap(), scoreboard(vec![
+            team("Team One", vec![solved(1, 600), first_solve(3, 3000)]),
+            // named after the members without a team name
+            team("v1, v2", vec![first_solve(2, 300), ProblemStatus::Incorrect]),
+        ]));
+    }
+
+    #[test]
+    fn all_participants() {
+        // practice rows are still left out
+        assert_eq!(parse_standings(FIXTURE, Participation::All).unwrap(), scoreboard(vec![
+            team("Team One", vec![solved(1, 600), solved(3, 3000)]),
+            team("v1, v2", vec![first_solve(2, 300), ProblemStatus::Incorrect]),
+            team("Guests", vec![ProblemStatus::Incorrect, first_solve(1, 1200)]),
+        ]));
+    }
+
+    #[test]
+    fn failed_request() {
+        let error = parse_standings(
+            r#"{"status": "FAILED", "comment": "contestId: Contest with id 1 not found"}"#,
+            Participation::Official
+        ).unwrap_err();
+
+        assert_eq!(error.to_string(), "contestId: Contest with id 1 not found");
+    }
+}
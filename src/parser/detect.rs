@@ -0,0 +1,81 @@
+use std::error::Error;
+use crate::parser::util::{fetch_text, Fetched};
+
+// decided from the host or file name alone, without fetching anything
+fn from_url(url: &str) -> Option<&'static str> {
+    let lower = url.to_lowercase();
+    let file = lower.trim_end_matches('/').rsplit('/').next().unwrap_or("");
+
+    if lower.contains("codeforces.com") {
+        Some("CODEFORCES")
+    } else if lower.contains("kattis.com") {
+        Some("KATTIS")
+    } else if lower.contains("/api/contests/") || ["scoreboard.json", "teams.json", "problems.json"].contains(&file) {
+        Some("WORLD FINALS")
+    } else if file == "results.xml" || file == "summary.html" {
+        Some("PC2")
+    } else {
+        None
+    }
+}
+
+fn from_content(content: &str) -> Result<&'static str, Box<dyn Error>> {
+    if content.contains("\"problemResults\"") {
+        Ok("CODEFORCES")
+    } else if content.contains("\"team_id\"") {
+        Ok("WORLD FINALS")
+    } else if content.contains("<contestStandings") || content.contains("PC^2") {
+        Ok("PC2")
+    } else if content.contains("standings-table") {
+        Ok("KATTIS")
+    } else if content.contains("data-ajax-id") {
+        Ok("CERC")
+    } else if content.contains("DOMjudge") || content.contains("scoretn") {
+        Err("DOMjudge scoreboards are not supported yet".into())
+    } else {
+        Err("Unable to detect the contest type, please select it manually".into())
+    }
+}
+
+// Contest type of a url, fetching the page if the url itself is not conclusive.
+// The fetched page is handed back so the parser does not download it again
+pub async fn detect(url: &str) -> Result<(&'static str, Option<Fetched>), Box<dyn Error>> {
+    if let Some(contest_type) = from_url(url) {
+        return Ok((contest_type, None));
+    }
+
+    let text = fetch_text(url).await?;
+    let contest_type = from_content(&text)?;
+
+    Ok((contest_type, Some(Fetched { url: url.to_string(), text })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{from_content, from_url};
+
+    #[test]
+    fn urls() {
+        assert_eq!(from_url("https://codeforces.com/gym/104757"), Some("CODEFORCES"));
+        assert_eq!(from_url("https://open.kattis.com/contests/nac24/standings"), Some("KATTIS"));
+        assert_eq!(from_url("https://example.org/api/contests/wf48"), Some("WORLD FINALS"));
+        assert_eq!(from_url("/data/wf48/scoreboard.json"), Some("WORLD FINALS"));
+        assert_eq!(from_url("/data/regional/results.xml"), Some("PC2"));
+        assert_eq!(from_url("https://example.org/Summary.html"), Some("PC2"));
+        assert_eq!(from_url("https://example.org/standings"), None);
+    }
+
+    #[test]
+    fn fixtures() {
+        let detected = |content: &str| from_content(content).ok();
+
+        assert_eq!(detected(include_str!("../../tests/fixtures/kattis.html")), Some("KATTIS"));
+        assert_eq!(detected(include_str!("../../tests/fixtures/cerc.html")), Some("CERC"));
+        assert_eq!(detected(include_str!("../../tests/fixtures/pc2_results.xml")), Some("PC2"));
+        assert_eq!(detected(include_str!("../../tests/fixtures/pc2_summary.html")), Some("PC2"));
+        assert_eq!(detected(include_str!("../../tests/fixtures/codeforces.json")), Some("CODEFORCES"));
+        assert_eq!(detected(include_str!("../../tests/fixtures/world_finals/scoreboard.json")), Some("WORLD FINALS"));
+        assert_eq!(detected("<html>Powered by DOMjudge</html>"), None);
+        assert_eq!(detected("<html></html>"), None);
+    }
+}
@@ -1,24 +1,24 @@
-use reqwest;
 use scraper::{Element, Html, Selector};
 use std::error::Error;
 use scraper::CaseSensitivity::AsciiCaseInsensitive;
 use scraper::selector::CssLocalName;
-use crate::scoreboard::{ProblemStatus, Scoreboard, TeamResult};
-
-pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn Error>> {
-    let html_content = reqwest::get(url).await?.text().await?;
-    let document = Html::parse_document(&html_content);
+use crate::parser::util::{fetch_pages, Fetched};
+use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
 
+// Rows of a single standings page.
+// Only rows present in the html are read, rows a page loads later by script are not fetched
+fn parse_page(document: &Html) -> Vec<TeamResult> {
     // Updated selectors based on the new HTML structure
     let table_selector = Selector::parse(".standings-table").unwrap();
     let team_row_selector = Selector::parse("tbody tr").unwrap();
-    let team_name_selector = Selector::parse(".standings-cell--expand a").unwrap();
-    let problem_cell_selector = Selector::parse("td.solved, td.attempted, td.first").unwrap();
+    let team_name_selector = Selector::parse(".standings-cell--expand a:not([href*=\"/universities/\"])").unwrap();
+    // kattis links the team's university below its name
+    let university_selector = Selector::parse(".standings-cell--expand a[href*=\"/universities/\"]").unwrap();
+    let problem_cell_selector = Selector::parse("td.solved, td.attempted, td.first, td.pending").unwrap();
     let result_cell_text_selector = Selector::parse(".standings-table-result-cell-text").unwrap();
     let time_selector = Selector::parse(".standings-table-result-cell-time").unwrap();
 
     let mut entries = Vec::new();
-    let mut num_problems = 0;
 
     if let Some(scoreboard_table) = document.select(&table_selector).next() {
         for team_row in scoreboard_table.select(&team_row_selector) {
@@ -37,11 +37,25 @@ pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn
                     .select(&result_cell_text_selector)
                     .next()
                     .map(|el| el.text().collect::<String>())
-                    .unwrap();
+                    .unwrap_or_default();
 
                 let status = if problem_cell.has_class(&CssLocalName::from("solved"), AsciiCaseInsensitive)
                     || problem_cell.has_class(&CssLocalName::from("first"), AsciiCaseInsensitive) {
-                    // Extract attempts and time
+                    // kattis only shows whole minutes
+                    let seconds = problem_cell
+                        .select(&time_selector)
+                        .next()
+                        .and_then(|el| parse_time_str(el.inner_html().trim()))
+                        .map(|minutes| minutes * 60);
+
+                    // a solve without a readable time can't be placed in the replay,
+                    // so the cell is left out rather than shown at 0:00
+                    let Some(seconds) = seconds else {
+                        problems.push(ProblemStatus::Incorrect);
+                        continue;
+                    };
+
+                    // Extract attempts
                     let attempts = cell_text
                         .trim()
                         .lines()
@@ -49,19 +63,29 @@ pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn
                         .and_then(|s| s.trim().parse::<usize>().ok())
                         .unwrap_or(1);
 
-                    let minutes = problem_cell
-                        .select(&time_selector)
-                        .next()
-                        .and_then(|el| parse_time_str(&el.inner_html().trim()))
-                        .unwrap_or(0);
-
                     let first_solve = problem_cell.has_class(&CssLocalName::from("first"), AsciiCaseInsensitive);
 
                     ProblemStatus::Solved {
                         attempts,
-                        minutes,
+                        seconds,
                         first_solve,
                     }
+                } else if problem_cell.has_class(&CssLocalName::from("pending"), AsciiCaseInsensitive) {
+                    // frozen cells read "tries + pending", e.g. "2 + 1"
+                    let mut counts = cell_text
+                        .split('+')
+                        .map(|c| c.trim().parse::<usize>().ok());
+
+                    let (attempts, pending) = match (counts.next().flatten(), counts.next().flatten()) {
+                        (Some(attempts), Some(pending)) => (attempts, pending),
+                        (Some(pending), None) => (0, pending),
+                        _ => (0, 1)
+                    };
+
+                    ProblemStatus::Frozen {
+                        attempts,
+                        pending,
+                    }
                 } else if problem_cell.has_class(&CssLocalName::from("attempted"), AsciiCaseInsensitive) {
                     ProblemStatus::Incorrect
                 } else {
@@ -71,17 +95,39 @@ pub async fn fetch_and_parse_scoreboard(url: &str) -> Result<Scoreboard, Box<dyn
                 problems.push(status);
             }
 
-            num_problems = num_problems.max(problems.len());
-            entries.push(TeamResult { team: team_name, problems });
+            let affiliation = team_row
+                .select(&university_selector)
+                .next()
+                .map(|el| el.text().collect::<String>().trim().to_string())
+                .filter(|u| !u.is_empty());
+
+            entries.push(TeamResult { affiliation, ..TeamResult::new(team_name, problems) });
         }
     }
 
-    let ret = Scoreboard {
+    entries
+}
+
+// kattis marks first solves itself, so they are not recomputed
+fn to_scoreboard(entries: Vec<TeamResult>) -> Scoreboard {
+    let num_problems = entries.iter()
+        .map(|e| e.problems.len())
+        .max()
+        .unwrap_or(0);
+
+    Scoreboard {
+        name: String::new(),
         num_problems,
+        problem_names: Vec::new(),
         entries,
-    };
+        awards: AwardRules::default(),
+    }
+}
 
-    Ok(ret)
+pub async fn fetch_and_parse_scoreboard(url: &str, fetched: Option<&Fetched>, progress: &(dyn Fn(String) + Sync)) -> Result<Scoreboard, Box<dyn Error>> {
+    let entries = fetch_pages(url, fetched, progress, parse_page).await?;
+
+    Ok(to_scoreboard(entries))
 }
 
 fn parse_time_str(time_str: &str) -> Option<usize> {
@@ -93,4 +139,57 @@ fn parse_time_str(time_str: &str) -> Option<usize> {
             .ok();
     }
     None
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+    use crate::test_util::{first_solve, solved, team};
+    use super::{parse_page, parse_time_str, to_scoreboard};
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/kattis.html");
+
+    #[test]
+    fn time_format() {
+        assert_eq!(parse_time_str("52 min"), Some(52));
+        assert_eq!(parse_time_str("0 min"), Some(0));
+        assert_eq!(parse_time_str("52"), None);
+        assert_eq!(parse_time_str("1:02:03"), None);
+    }
+
+    #[test]
+    fn fixture_scoreboard() {
+        let sb = to_scoreboard(parse_page(&Html::parse_document(FIXTURE)));
+
+        assert_eq!(sb, Scoreboard {
+            name: String::new(),
+            num_problems: 3,
+            problem_names: Vec::new(),
+            entries: vec![
+                TeamResult {
+                    affiliation: Some("UC San Diego".to_string()),
+                    ..team("Alpha", vec![
+                        first_solve(1, 12 * 60),
+                        solved(3, 95 * 60),
+                        ProblemStatus::Incorrect,
+                    ])
+                },
+                team("Beta", vec![
+                    solved(2, 30 * 60),
+                    // "2 + 1"
+                    ProblemStatus::Frozen { attempts: 2, pending: 1 },
+                    // only the pending count
+                    ProblemStatus::Frozen { attempts: 0, pending: 1 },
+                ]),
+                team("Gamma", vec![
+                    // solved, but without a time
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Incorrect,
+                ]),
+            ],
+            awards: AwardRules::default(),
+        });
+    }
+}
@@ -1,42 +1,52 @@
 use std::error::Error;
-use quarve::core::slock_owner;
-use quarve::state::{Binding, Filterless};
-use quarve::state::SetAction::Set;
-use quarve::view::modal::{MessageBox, MessageBoxButton};
-use crate::scoreboard::{Scoreboard, ScoreboardOption};
+use crate::parser::codeforces::Participation;
+use crate::scoreboard::Scoreboard;
 
-mod kattis;
-mod cerc;
-mod util;
+pub mod kattis;
+pub mod cerc;
+pub mod codeforces;
+pub mod pc2;
+pub mod world_finals;
+pub mod util;
+pub mod detect;
 mod asia_jakarta;
 mod nerc;
 
-pub fn handle(f: Result<Scoreboard, Box<dyn Error>>, result: impl Binding<Filterless<ScoreboardOption>>) {
-    match f {
-        Ok(scoreboard) => {
-            let s = slock_owner();
-            result.apply(Set(ScoreboardOption::Some(scoreboard)), s.marker());
-        }
-        Err(e) => {
-            MessageBox::new(
-                Some("Operation Failed"),
-                Some(&e.to_string())
-            )
-                .button(MessageBoxButton::Ok)
-                .run(|_, _| {})
-        }
+// values of the contest type understood by parse,
+// "AUTO" picks one based on the url and page content
+pub const CONTEST_TYPES: [&str; 8] = [
+    "AUTO", "KATTIS", "CERC", "PC2", "WORLD FINALS",
+    "CODEFORCES", "CODEFORCES (VIRTUAL)", "CODEFORCES (ALL)"
+];
+
+async fn parse_as(contest_type: &str, url: &str, progress: &(dyn Fn(String) + Sync)) -> Result<Scoreboard, Box<dyn Error>> {
+    let (contest_type, fetched) = match contest_type {
+        "AUTO" => detect::detect(url).await?,
+        other => (other, None)
+    };
+    let fetched = fetched.as_ref();
+
+    match contest_type {
+        "KATTIS" => kattis::fetch_and_parse_scoreboard(url, fetched, progress).await,
+        "CERC" => cerc::fetch_and_parse_scoreboard(url, fetched, progress).await,
+        "PC2" => pc2::fetch_and_parse_scoreboard(url, fetched).await,
+        "WORLD FINALS" => world_finals::fetch_and_parse_scoreboard(url, fetched).await,
+        "CODEFORCES" => codeforces::fetch_and_parse_scoreboard(url, fetched, Participation::Official).await,
+        "CODEFORCES (VIRTUAL)" => codeforces::fetch_and_parse_scoreboard(url, fetched, Participation::OfficialAndVirtual).await,
+        "CODEFORCES (ALL)" => codeforces::fetch_and_parse_scoreboard(url, fetched, Participation::All).await,
+        other => Err(format!("Unknown contest type {}", other).into())
+    }
+}
+
+// loads the scoreboard at url, contest_type being one of CONTEST_TYPES
+// progress is told about each page of paginated standings
+pub async fn parse(contest_type: &str, url: &str, progress: &(dyn Fn(String) + Sync)) -> Result<Scoreboard, Box<dyn Error>> {
+    let sb = parse_as(contest_type, url, progress).await?;
+
+    // a parser given the wrong kind of page usually finds nothing rather than failing
+    if sb.entries.is_empty() {
+        Err("No teams found on this scoreboard, check the contest type".into())
+    } else {
+        Ok(sb)
     }
 }
-pub async fn begin_parse(contest_type: &str, url: &str, result: impl Binding<Filterless<ScoreboardOption>>) {
-    match contest_type {
-        "KATTIS" => {
-            handle(kattis::fetch_and_parse_scoreboard(url).await, result);
-        },
-        "CERC" => {
-            handle(cerc::fetch_and_parse_scoreboard(url).await, result);
-        }
-        &_ => {
-            unreachable!()
-        }
-    };
-}
\ No newline at end of file
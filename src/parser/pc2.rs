@@ -0,0 +1,223 @@
+use std::error::Error;
+use scraper::{Html, Selector};
+use crate::parser::util::{add_first_solves, fetch_text_reusing, Fetched};
+use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+
+// pc2 reports times in whole minutes
+fn solved(attempts: usize, minutes: usize) -> ProblemStatus {
+    ProblemStatus::Solved {
+        attempts,
+        seconds: minutes * 60,
+        first_solve: false,
+    }
+}
+
+// results.xml as written by the pc2 scoreboard module
+pub fn parse_results_xml(xml: &str) -> Result<Scoreboard, Box<dyn Error>> {
+    let document = roxmltree::Document::parse(xml)?;
+
+    let problem_names = document.descendants()
+        .filter(|n| n.has_tag_name("problem"))
+        .map(|n| n.attribute("title").unwrap_or("").to_string())
+        .collect::<Vec<_>>();
+    let num_problems = problem_names.len();
+    let name = document.descendants()
+        .find(|n| n.has_tag_name("standingsHeader"))
+        .and_then(|n| n.attribute("title"))
+        .unwrap_or("")
+        .to_string();
+
+    let entries = document.descendants()
+        .filter(|n| n.has_tag_name("teamStanding"))
+        .map(|team| {
+            let mut problems = vec![ProblemStatus::Incorrect; num_problems];
+
+            for info in team.children().filter(|n| n.has_tag_name("problemSummaryInfo")) {
+                // problems are 1-indexed
+                let Some(index) = info.attribute("index")
+                    .and_then(|i| i.parse::<usize>().ok())
+                    .and_then(|i| i.checked_sub(1))
+                    .filter(|i| *i < num_problems) else {
+                    continue;
+                };
+
+                let attempts = info.attribute("attempts")
+                    .and_then(|a| a.parse::<usize>().ok())
+                    .unwrap_or(0);
+
+                problems[index] = if info.attribute("isSolved") == Some("true") {
+                    let minutes = info.attribute("solutionTime")
+                        .and_then(|t| t.parse::<usize>().ok())
+                        .unwrap_or(0);
+
+                    solved(attempts.max(1), minutes)
+                } else if info.attribute("isPending") == Some("true") {
+                    ProblemStatus::Pending { attempts }
+                } else {
+                    ProblemStatus::Incorrect
+                };
+            }
+
+            let affiliation = team.attribute("shortSchoolName")
+                .map(|school| school.trim().to_string())
+                .filter(|school| !school.is_empty());
+
+            TeamResult {
+                affiliation,
+                ..TeamResult::new(team.attribute("teamName").unwrap_or("Unknown Team").to_string(), problems)
+            }
+        })
+        .collect();
+
+    let mut res = Scoreboard {
+        name,
+        num_problems,
+        problem_names,
+        entries,
+        awards: AwardRules::default(),
+    };
+    add_first_solves(&mut res);
+
+    Ok(res)
+}
+
+// summary.html, where each problem cell reads "attempts/minutes" or "attempts/--"
+pub fn parse_summary_html(html: &str) -> Result<Scoreboard, Box<dyn Error>> {
+    let document = Html::parse_document(html);
+
+    let row_selector = Selector::parse("tr").unwrap();
+    let header_selector = Selector::parse("th, td").unwrap();
+    let cell_selector = Selector::parse("td").unwrap();
+
+    let mut rows = document.select(&row_selector);
+
+    // problem columns sit between "Time" and the trailing totals column
+    let header = rows.by_ref()
+        .map(|row| row.select(&header_selector).collect::<Vec<_>>())
+        .find(|cells| cells.iter().any(|c| c.text().collect::<String>().trim() == "Rank"))
+        .ok_or("Unable to find the standings header")?;
+
+    let first_problem = header.iter()
+        .position(|c| c.text().collect::<String>().trim() == "Time")
+        .ok_or("Unable to find the time column")? + 1;
+    let problem_headers = header[first_problem..].iter()
+        .take_while(|c| !c.text().collect::<String>().contains("Total"))
+        .collect::<Vec<_>>();
+
+    let num_problems = problem_headers.len();
+    let problem_names = problem_headers.iter()
+        .map(|c| c.value().attr("title").unwrap_or("").to_string())
+        .collect();
+
+    let entries = rows
+        .map(|row| row.select(&cell_selector)
+            .map(|c| c.text().collect::<String>().trim().to_string())
+            .collect::<Vec<_>>()
+        )
+        // skips the trailing per problem summary row
+        .filter(|cells| cells.len() >= first_problem + num_problems && cells[0].parse::<usize>().is_ok())
+        .map(|cells| {
+            let problems = cells[first_problem..first_problem + num_problems].iter()
+                .map(|cell| {
+                    let (attempts, time) = cell.split_once('/').unwrap_or((cell, "--"));
+                    let attempts = attempts.trim().parse::<usize>().unwrap_or(0);
+
+                    match time.trim().parse::<usize>() {
+                        Ok(minutes) if attempts > 0 => solved(attempts, minutes),
+                        _ => ProblemStatus::Incorrect
+                    }
+                })
+                .collect();
+
+            // unlike results.xml the summary has no school column
+            TeamResult::new(cells[1].clone(), problems)
+        })
+        .collect();
+
+    let mut res = Scoreboard {
+        name: String::new(),
+        num_problems,
+        problem_names,
+        entries,
+        awards: AwardRules::default(),
+    };
+    add_first_solves(&mut res);
+
+    Ok(res)
+}
+
+pub async fn fetch_and_parse_scoreboard(url: &str, fetched: Option<&Fetched>) -> Result<Scoreboard, Box<dyn Error>> {
+    let content = fetch_text_reusing(url, fetched).await?;
+
+    if content.contains("<contestStandings") {
+        parse_results_xml(&content)
+    } else {
+        parse_summary_html(&content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+    use crate::test_util::{first_solve, solved, team};
+    use super::{parse_results_xml, parse_summary_html};
+
+    const RESULTS: &str = include_str!("../../tests/fixtures/pc2_results.xml");
+    const SUMMARY: &str = include_str!("../../tests/fixtures/pc2_summary.html");
+
+    #[test]
+    fn results_xml() {
+        let sb = parse_results_xml(RESULTS).unwrap();
+
+        assert_eq!(sb, Scoreboard {
+            name: "Practice".to_string(),
+            num_problems: 3,
+            problem_names: vec!["Apples".to_string(), "Bananas".to_string(), "Cherries".to_string()],
+            entries: vec![
+                TeamResult {
+                    affiliation: Some("UCSD".to_string()),
+                    ..team("Team One", vec![
+                        solved(1, 20 * 60),
+                        first_solve(3, 80 * 60),
+                        ProblemStatus::Incorrect,
+                    ])
+                },
+                // no school given, problem 2 is missing and problem 7 does not exist
+                team("Team Two", vec![
+                    first_solve(2, 15 * 60),
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Pending { attempts: 1 },
+                ]),
+            ],
+            awards: AwardRules::default(),
+        });
+    }
+
+    #[test]
+    fn summary_html() {
+        let sb = parse_summary_html(SUMMARY).unwrap();
+
+        assert_eq!(sb, Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: vec!["Apples".to_string(), "Bananas".to_string()],
+            entries: vec![
+                team("Team One", vec![
+                    solved(1, 20 * 60),
+                    first_solve(3, 60 * 60),
+                ]),
+                team("Team Two", vec![
+                    first_solve(2, 15 * 60),
+                    ProblemStatus::Incorrect,
+                ]),
+            ],
+            awards: AwardRules::default(),
+        });
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(parse_results_xml("<contestStandings>").is_err());
+        assert!(parse_summary_html("<html><body>Not started</body></html>").is_err());
+    }
+}
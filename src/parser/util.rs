@@ -1,28 +1,177 @@
-use crate::scoreboard::{ProblemStatus, Scoreboard};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs;
+use scraper::{Html, Selector};
+use crate::scoreboard::{ProblemStatus, Scoreboard, TeamResult};
 
+// guards against pagination links that go around in circles
+const MAX_PAGES: usize = 100;
+
+// fetches http(s) urls, anything else is read as a local file
+pub async fn fetch_text(url: &str) -> Result<String, Box<dyn Error>> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(reqwest::get(url).await?.error_for_status()?.text().await?)
+    } else {
+        Ok(fs::read_to_string(url.trim_start_matches("file://"))?)
+    }
+}
+
+// a page that has already been downloaded, e.g. to detect the contest type
+pub struct Fetched {
+    pub url: String,
+    pub text: String,
+}
+
+// fetch_text, unless url is the page already downloaded
+pub async fn fetch_text_reusing(url: &str, fetched: Option<&Fetched>) -> Result<String, Box<dyn Error>> {
+    match fetched {
+        Some(f) if f.url == url => Ok(f.text.clone()),
+        _ => fetch_text(url).await
+    }
+}
+
+// absolute url of the following page of a paginated scoreboard, if any
+fn next_page(document: &Html, url: &str) -> Option<String> {
+    let selector = Selector::parse("a[rel~=\"next\"], .pagination .next a, a.next").unwrap();
+    let href = document.select(&selector).next()?.value().attr("href")?;
+
+    // local files have nothing to page through
+    let base = reqwest::Url::parse(url).ok()?;
+    base.join(href).ok().map(|u| u.to_string())
+}
+
+// Appends the teams of a page and returns their names.
+// Pages can overlap while the standings change underneath us, so a team also listed
+// on the previous page is only taken once. Teams sharing a name on the same page are all kept
+fn add_page(entries: &mut Vec<TeamResult>, previous: &HashSet<String>, page: Vec<TeamResult>) -> HashSet<String> {
+    let names = page.iter()
+        .map(|team| team.team.clone())
+        .collect();
+
+    entries.extend(page.into_iter().filter(|team| !previous.contains(&team.team)));
+    names
+}
+
+// Large contests split the standings over several pages,
+// which are followed until one links to no further page
+pub async fn fetch_pages(
+    url: &str,
+    fetched: Option<&Fetched>,
+    progress: &(dyn Fn(String) + Sync),
+    parse_page: fn(&Html) -> Vec<TeamResult>
+) -> Result<Vec<TeamResult>, Box<dyn Error>> {
+    let mut entries = Vec::new();
+    let mut previous = HashSet::new();
+    let mut visited = Vec::new();
+    let mut page_url = Some(url.to_string());
+
+    while let Some(current) = page_url.take() {
+        if visited.contains(&current) || visited.len() >= MAX_PAGES {
+            break;
+        }
+
+        let html_content = fetch_text_reusing(&current, fetched).await?;
+        let document = Html::parse_document(&html_content);
+
+        previous = add_page(&mut entries, &previous, parse_page(&document));
+
+        page_url = next_page(&document, &current);
+        visited.push(current);
+        progress(format!("Loaded page {} ({} teams)", visited.len(), entries.len()));
+    }
+
+    Ok(entries)
+}
+
+// marks the earliest solve of each problem, the team listed first wins ties
 pub fn add_first_solves(sb: &mut Scoreboard) {
     for i in 0 .. sb.num_problems {
+        // rows of html scoreboards can be shorter than num_problems
         let min = sb.entries.iter()
-            .map(|e| match &e.problems[i] {
-                ProblemStatus::Incorrect => { usize::MAX }
-                ProblemStatus::Solved { minutes, .. } => {
-                    *minutes
-                }
+            .filter_map(|e| match e.problems.get(i) {
+                Some(ProblemStatus::Solved { seconds, .. }) => Some(*seconds),
+                _ => None
             })
             .min();
 
         if let Some(m) = min {
             for p in sb.entries.iter_mut() {
-                match &mut p.problems[i] {
-                    ProblemStatus::Incorrect => {}
-                    ProblemStatus::Solved { minutes, first_solve, .. } => {
-                        if *minutes == m {
-                            *first_solve = true;
-                            break;
-                        }
+                if let Some(ProblemStatus::Solved { seconds, first_solve, .. }) = p.problems.get_mut(i) {
+                    if *seconds == m {
+                        *first_solve = true;
+                        break;
                     }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard, TeamResult};
+    use std::collections::HashSet;
+    use crate::test_util::{first_solve, solved, team};
+    use super::{add_first_solves, add_page, fetch_text_reusing, next_page, Fetched};
+
+    fn scoreboard(entries: Vec<TeamResult>) -> Scoreboard {
+        Scoreboard { name: String::new(), num_problems: 3, problem_names: Vec::new(), entries, awards: AwardRules::default() }
+    }
+
+    #[test]
+    fn first_solves() {
+        let mut sb = scoreboard(vec![
+            team("A", vec![solved(1, 600), solved(1, 300), ProblemStatus::Incorrect]),
+            team("B", vec![solved(1, 120), solved(1, 300), ProblemStatus::Pending { attempts: 1 }]),
+            // shorter row
+            team("C", vec![ProblemStatus::Frozen { attempts: 0, pending: 2 }]),
+        ]);
+        add_first_solves(&mut sb);
+
+        assert_eq!(sb, scoreboard(vec![
+            team("A", vec![solved(1, 600), first_solve(1, 300), ProblemStatus::Incorrect]),
+            team("B", vec![first_solve(1, 120), solved(1, 300), ProblemStatus::Pending { attempts: 1 }]),
+            team("C", vec![ProblemStatus::Frozen { attempts: 0, pending: 2 }]),
+        ]));
+    }
+
+    #[test]
+    fn overlapping_pages() {
+        let mut entries = Vec::new();
+
+        // namesakes on one page are different teams
+        let first = add_page(&mut entries, &HashSet::new(), vec![team("A", Vec::new()), team("Unknown Team", Vec::new()), team("Unknown Team", Vec::new())]);
+        // "A" dropped to the second page while it was being fetched
+        add_page(&mut entries, &first, vec![team("A", Vec::new()), team("B", Vec::new())]);
+
+        let names = entries.iter().map(|e| e.team.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["A", "Unknown Team", "Unknown Team", "B"]);
+    }
+
+    #[test]
+    fn next_pages() {
+        let page = |html: &str| Html::parse_document(html);
+
+        assert_eq!(
+            next_page(&page(r#"<a rel="next" href="?page=2">Next</a>"#), "https://example.org/standings?page=1").as_deref(),
+            Some("https://example.org/standings?page=2")
+        );
+        assert_eq!(
+            next_page(&page(r#"<ul class="pagination"><li class="next"><a href="/standings/3">3</a></li></ul>"#), "https://example.org/standings/2").as_deref(),
+            Some("https://example.org/standings/3")
+        );
+        assert_eq!(next_page(&page(r#"<a href="?page=2">2</a>"#), "https://example.org/standings"), None);
+        // local files have no base to resolve against
+        assert_eq!(next_page(&page(r#"<a rel="next" href="page2.html">Next</a>"#), "saved/page1.html"), None);
+    }
+
+    #[tokio::test]
+    async fn reuses_fetched_page() {
+        let fetched = Fetched { url: "missing/standings.html".to_string(), text: "<html></html>".to_string() };
+
+        // the file does not exist, so only the page at hand can be returned
+        assert_eq!(fetch_text_reusing("missing/standings.html", Some(&fetched)).await.ok().as_deref(), Some("<html></html>"));
+        assert!(fetch_text_reusing("missing/other.html", Some(&fetched)).await.is_err());
+    }
+}
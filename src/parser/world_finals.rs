@@ -0,0 +1,283 @@
+use std::collections::HashMap;
+use std::error::Error;
+use serde::Deserialize;
+use serde_json::Value;
+use crate::parser::util::{add_first_solves, fetch_text_reusing, Fetched};
+use crate::scoreboard::{AwardRules, MedalLines, ProblemStatus, Scoreboard, TeamResult};
+
+#[derive(Deserialize)]
+struct Problem {
+    id: String,
+    name: Option<String>,
+    ordinal: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct Team {
+    id: String,
+    name: String,
+    display_name: Option<String>,
+    organization_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Organization {
+    id: String,
+    name: String,
+    formal_name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Standings {
+    rows: Vec<Row>,
+}
+
+#[derive(Deserialize)]
+struct Row {
+    team_id: String,
+    problems: Vec<ProblemResult>,
+}
+
+#[derive(Deserialize)]
+struct ProblemResult {
+    problem_id: String,
+    num_judged: usize,
+    #[serde(default)]
+    num_pending: usize,
+    solved: bool,
+    time: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Award {
+    id: String,
+    citation: String,
+    team_ids: Vec<String>,
+}
+
+// older archives give whole minutes, newer ones a relative time such as "1:23:45.000"
+fn solve_seconds(time: &Value) -> Option<usize> {
+    match time {
+        Value::Number(minutes) => Some(minutes.as_u64()? as usize * 60),
+        Value::String(reltime) => {
+            let reltime = reltime.split('.').next()?;
+            let mut parts = reltime.split(':').map(|p| p.parse::<usize>().ok());
+            let hours = parts.next()??;
+            let minutes = parts.next()??;
+            let seconds = parts.next()??;
+
+            Some(hours * 3600 + minutes * 60 + seconds)
+        }
+        _ => None
+    }
+}
+
+// Accepts the directory of a CLICS contest archive (local or remote),
+// any json file inside it, or a contest api url such as
+// https://example.org/api/contests/wf48
+fn endpoint(base: &str, name: &str) -> String {
+    let base = match base.rsplit_once('/') {
+        Some((dir, file)) if file.ends_with(".json") => dir,
+        _ => base.trim_end_matches('/'),
+    };
+
+    if base.contains("/api/") {
+        format!("{}/{}", base, name)
+    } else {
+        format!("{}/{}.json", base, name)
+    }
+}
+
+pub fn parse_archive(
+    problems: &str,
+    teams: &str,
+    organizations: Option<&str>,
+    scoreboard: &str,
+    awards: Option<&str>,
+) -> Result<Scoreboard, Box<dyn Error>> {
+    let mut problems: Vec<Problem> = serde_json::from_str(problems)?;
+    problems.sort_by_key(|p| p.ordinal);
+    let problem_index = problems.iter()
+        .enumerate()
+        .map(|(i, p)| (p.id.as_str(), i))
+        .collect::<HashMap<_, _>>();
+
+    let organizations: Vec<Organization> = match organizations {
+        Some(organizations) => serde_json::from_str(organizations)?,
+        None => Vec::new()
+    };
+    let organization_names = organizations.into_iter()
+        .map(|o| (o.id, o.formal_name.unwrap_or(o.name)))
+        .collect::<HashMap<_, _>>();
+
+    let teams: Vec<Team> = serde_json::from_str(teams)?;
+    let teams = teams.into_iter()
+        .map(|t| {
+            let affiliation = t.organization_id
+                .and_then(|o| organization_names.get(&o).cloned());
+            (t.id, (t.display_name.unwrap_or(t.name), affiliation))
+        })
+        .collect::<HashMap<_, _>>();
+
+    let awards: Vec<Award> = match awards {
+        Some(awards) => serde_json::from_str(awards)?,
+        None => Vec::new()
+    };
+    let champions = awards.iter()
+        .filter(|a| a.id.starts_with("group-winner"))
+        .flat_map(|a| a.team_ids.iter().map(|t| (t.as_str(), a.citation.clone())))
+        .collect::<HashMap<_, _>>();
+
+    let standings: Standings = serde_json::from_str(scoreboard)?;
+    let entries = standings.rows.into_iter()
+        .map(|row| {
+            let mut problems_status = vec![ProblemStatus::Incorrect; problems.len()];
+
+            for result in row.problems {
+                let Some(&index) = problem_index.get(result.problem_id.as_str()) else {
+                    continue;
+                };
+
+                problems_status[index] = match result.time.as_ref().and_then(solve_seconds) {
+                    Some(seconds) if result.solved => ProblemStatus::Solved {
+                        attempts: result.num_judged.max(1),
+                        seconds,
+                        first_solve: false,
+                    },
+                    _ if result.num_pending > 0 => ProblemStatus::Pending {
+                        attempts: result.num_judged + result.num_pending,
+                    },
+                    _ => ProblemStatus::Incorrect
+                };
+            }
+
+            let (team, affiliation) = teams.get(&row.team_id)
+                .cloned()
+                .unwrap_or((row.team_id.clone(), None));
+
+            TeamResult {
+                team,
+                problems: problems_status,
+                regional_champion: champions.get(row.team_id.as_str()).cloned(),
+                affiliation,
+            }
+        })
+        .collect();
+
+    let medal_count = |id: &str| awards.iter()
+        .find(|a| a.id == id)
+        .map_or(0, |a| a.team_ids.len());
+    let medals = MedalLines {
+        gold: medal_count("gold-medal"),
+        silver: medal_count("silver-medal"),
+        bronze: medal_count("bronze-medal"),
+    };
+
+    let mut res = Scoreboard {
+        name: String::new(),
+        num_problems: problems.len(),
+        problem_names: problems.into_iter()
+            .map(|p| p.name.unwrap_or_default())
+            .collect(),
+        entries,
+        awards: AwardRules {
+            medals: Some(medals).filter(|m| m.total() > 0),
+            advancement: None,
+            honorable_mention: awards.iter().any(|a| a.id == "honorable-mention"),
+        },
+    };
+    add_first_solves(&mut res);
+
+    Ok(res)
+}
+
+pub async fn fetch_and_parse_scoreboard(url: &str, fetched: Option<&Fetched>) -> Result<Scoreboard, Box<dyn Error>> {
+    let problems = fetch_text_reusing(&endpoint(url, "problems"), fetched).await?;
+    let teams = fetch_text_reusing(&endpoint(url, "teams"), fetched).await?;
+    let scoreboard = fetch_text_reusing(&endpoint(url, "scoreboard"), fetched).await?;
+    // not every archive publishes organizations or awards
+    let organizations = fetch_text_reusing(&endpoint(url, "organizations"), fetched).await.ok();
+    let awards = fetch_text_reusing(&endpoint(url, "awards"), fetched).await.ok();
+
+    parse_archive(&problems, &teams, organizations.as_deref(), &scoreboard, awards.as_deref())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use crate::scoreboard::{AwardRules, MedalLines, ProblemStatus, Scoreboard, TeamResult};
+    use crate::test_util::{first_solve, solved};
+    use super::{endpoint, parse_archive, solve_seconds};
+
+    const PROBLEMS: &str = include_str!("../../tests/fixtures/world_finals/problems.json");
+    const TEAMS: &str = include_str!("../../tests/fixtures/world_finals/teams.json");
+    const ORGANIZATIONS: &str = include_str!("../../tests/fixtures/world_finals/organizations.json");
+    const SCOREBOARD: &str = include_str!("../../tests/fixtures/world_finals/scoreboard.json");
+    const AWARDS: &str = include_str!("../../tests/fixtures/world_finals/awards.json");
+
+    fn team(name: &str, problems: Vec<ProblemStatus>, regional_champion: Option<&str>, affiliation: Option<&str>) -> TeamResult {
+        TeamResult {
+            regional_champion: regional_champion.map(|c| c.to_string()),
+            affiliation: affiliation.map(|a| a.to_string()),
+            ..TeamResult::new(name.to_string(), problems)
+        }
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(solve_seconds(&json!(20)), Some(20 * 60));
+        assert_eq!(solve_seconds(&json!("2:05:30.000")), Some(2 * 3600 + 5 * 60 + 30));
+        assert_eq!(solve_seconds(&json!("2:05:30")), Some(2 * 3600 + 5 * 60 + 30));
+        assert_eq!(solve_seconds(&json!("2:05")), None);
+        assert_eq!(solve_seconds(&json!(null)), None);
+    }
+
+    #[test]
+    fn endpoints() {
+        assert_eq!(endpoint("https://example.org/api/contests/wf48", "teams"), "https://example.org/api/contests/wf48/teams");
+        assert_eq!(endpoint("https://example.org/api/contests/wf48/", "teams"), "https://example.org/api/contests/wf48/teams");
+        assert_eq!(endpoint("/data/wf48/scoreboard.json", "teams"), "/data/wf48/teams.json");
+        assert_eq!(endpoint("/data/wf48/", "awards"), "/data/wf48/awards.json");
+    }
+
+    #[test]
+    fn full_archive() {
+        let sb = parse_archive(PROBLEMS, TEAMS, Some(ORGANIZATIONS), SCOREBOARD, Some(AWARDS)).unwrap();
+
+        assert_eq!(sb, Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            // sorted by ordinal
+            problem_names: vec!["Apples".to_string(), "Bananas".to_string()],
+            entries: vec![
+                team("Team One", vec![
+                    solved(1, 20 * 60),
+                    first_solve(3, 2 * 3600 + 5 * 60 + 30),
+                ], None, Some("University One")),
+                team("Team Two", vec![
+                    first_solve(2, 15 * 60),
+                    ProblemStatus::Pending { attempts: 3 },
+                ], Some("Champions of Europe"), Some("University Two")),
+                // unknown problems are ignored
+                team("Team Three", vec![
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Incorrect,
+                ], None, None),
+            ],
+            awards: AwardRules {
+                medals: Some(MedalLines { gold: 1, silver: 1, bronze: 0 }),
+                advancement: None,
+                honorable_mention: true,
+            },
+        });
+    }
+
+    #[test]
+    fn without_optional_files() {
+        let sb = parse_archive(PROBLEMS, TEAMS, None, SCOREBOARD, None).unwrap();
+
+        assert_eq!(sb.awards, AwardRules::default());
+        assert!(sb.entries.iter().all(|e| e.regional_champion.is_none() && e.affiliation.is_none()));
+        assert_eq!(sb.entries.iter().map(|e| e.team.as_str()).collect::<Vec<_>>(), vec!["Team One", "Team Two", "Team Three"]);
+    }
+}
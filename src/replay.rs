@@ -0,0 +1,95 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use chrono::{Local, NaiveTime, TimeZone};
+use crate::scoreboard::CONTEST_MINUTES;
+
+// seconds elapsed since the replay started (stopping at the end of the contest),
+// and seconds remaining until it starts
+pub fn replay_clock(start: SystemTime, now: SystemTime) -> (usize, usize) {
+    match now.duration_since(start) {
+        Ok(duration) => ((duration.as_secs() as usize).min(CONTEST_MINUTES * 60), 0),
+        // rounded up so that the countdown never shows 0 before the start
+        Err(e) => {
+            let remaining = e.duration();
+            (0, remaining.as_secs() as usize + usize::from(remaining.subsec_nanos() > 0))
+        }
+    }
+}
+
+// "HH:MM:SS"
+pub fn format_clock(seconds: usize) -> String {
+    let minutes = seconds / 60;
+
+    format!("{:0>2}:{:0>2}:{:0>2}", minutes / 60, minutes % 60, seconds % 60)
+}
+
+// Accepts an empty string (start now), "+N" (start in N minutes)
+// or a local wall-clock time "HH:MM" today
+pub fn parse_start(input: &str, now: SystemTime) -> Result<u64, String> {
+    let input = input.trim();
+    let now_secs = now.duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+
+    if input.is_empty() {
+        return Ok(now_secs);
+    }
+
+    if let Some(delay) = input.strip_prefix('+') {
+        let minutes = delay.trim().parse::<u64>()
+            .map_err(|_| format!("Invalid delay \"{}\"", input))?;
+        return Ok(now_secs + minutes * 60);
+    }
+
+    let time = NaiveTime::parse_from_str(input, "%H:%M")
+        .map_err(|_| format!("Invalid start time \"{}\", expected HH:MM or +minutes", input))?;
+    let today = Local::now().date_naive().and_time(time);
+    let start = Local.from_local_datetime(&today)
+        .earliest()
+        .ok_or_else(|| format!("Start time \"{}\" does not exist today", input))?;
+
+    u64::try_from(start.timestamp())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use chrono::{Local, TimeZone, Timelike};
+    use crate::scoreboard::CONTEST_MINUTES;
+    use super::{format_clock, parse_start, replay_clock};
+
+    #[test]
+    fn clock() {
+        let start = UNIX_EPOCH + Duration::from_secs(1000);
+
+        assert_eq!(replay_clock(start, start), (0, 0));
+        assert_eq!(replay_clock(start, start + Duration::from_secs(90)), (90, 0));
+        assert_eq!(replay_clock(start, start + Duration::from_secs(24 * 3600)), (CONTEST_MINUTES * 60, 0));
+        assert_eq!(replay_clock(start, start - Duration::from_millis(59_500)), (0, 60));
+        assert_eq!(replay_clock(start, start - Duration::from_secs(60)), (0, 60));
+        assert_eq!(replay_clock(start, start - Duration::from_millis(60_001)), (0, 61));
+    }
+
+    #[test]
+    fn start() {
+        let now = UNIX_EPOCH + Duration::from_secs(1000);
+
+        assert_eq!(parse_start("", now), Ok(1000));
+        assert_eq!(parse_start(" +15 ", now), Ok(1000 + 15 * 60));
+        assert!(parse_start("+soon", now).is_err());
+        assert!(parse_start("25:00", now).is_err());
+        assert!(parse_start("10", now).is_err());
+
+        let start = parse_start("10:30", now).unwrap();
+        let local = Local.timestamp_opt(start as i64, 0).unwrap();
+        assert_eq!((local.hour(), local.minute(), local.second()), (10, 30, 0));
+        assert_eq!(local.date_naive(), Local::now().date_naive());
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(format_clock(0), "00:00:00");
+        assert_eq!(format_clock(3723), "01:02:03");
+        assert_eq!(format_clock(CONTEST_MINUTES * 60), "05:00:00");
+    }
+}
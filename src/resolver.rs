@@ -0,0 +1,197 @@
+use crate::scoreboard::{ProblemStatus, Scoreboard, TeamResult, CONTEST_MINUTES};
+
+// a single frozen submission being shown
+// cells that were still frozen in the source have no known verdict,
+// so they are revealed as not accepted
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Reveal {
+    pub team: usize,
+    pub problem: usize,
+    pub accepted: bool,
+}
+
+// a team as currently displayed by the resolver
+#[derive(Clone, PartialEq, Debug)]
+pub struct ResolverRow {
+    // index into the original scoreboard entries
+    pub index: usize,
+    // results with all unrevealed submissions hidden
+    pub visible: TeamResult,
+    pub pending: Vec<bool>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Resolver {
+    entries: Vec<TeamResult>,
+    pending: Vec<Vec<bool>>,
+    last_reveal: Option<Reveal>,
+}
+
+impl Resolver {
+    pub fn new(sb: &Scoreboard, freeze_minutes: usize) -> Resolver {
+        let pending = sb.entries.iter()
+            .map(|e| e.problems.iter()
+                .map(|p| match p {
+                    ProblemStatus::Incorrect | ProblemStatus::Pending { .. } => false,
+                    ProblemStatus::Frozen { .. } => true,
+                    ProblemStatus::Solved { seconds, .. } => *seconds >= freeze_minutes * 60
+                })
+                .collect()
+            )
+            .collect();
+
+        Resolver {
+            entries: sb.entries.clone(),
+            pending,
+            last_reveal: None,
+        }
+    }
+
+    pub fn last_reveal(&self) -> Option<Reveal> {
+        self.last_reveal
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.pending.iter().all(|p| p.iter().all(|x| !x))
+    }
+
+    // standings with only revealed submissions counted, best team first
+    pub fn standings(&self) -> Vec<ResolverRow> {
+        let mut rows = self.entries.iter()
+            .zip(self.pending.iter())
+            .enumerate()
+            .map(|(index, (team, pending))| {
+                let problems = team.problems.iter()
+                    .zip(pending.iter())
+                    .map(|(p, hidden)| match p {
+                        // revealed frozen cells have no verdict to show
+                        _ if *hidden => ProblemStatus::Incorrect,
+                        ProblemStatus::Frozen { .. } => ProblemStatus::Incorrect,
+                        _ => p.clone()
+                    })
+                    .collect();
+
+                ResolverRow {
+                    index,
+                    visible: TeamResult {
+                        team: team.team.clone(),
+                        problems,
+                        regional_champion: team.regional_champion.clone(),
+                        affiliation: team.affiliation.clone(),
+                    },
+                    pending: pending.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        rows.sort_by(|r1, r2| {
+            r1.visible.score(CONTEST_MINUTES * 60).cmp(&r2.visible.score(CONTEST_MINUTES * 60))
+        });
+
+        rows
+    }
+
+    // reveals the leftmost pending problem of the lowest ranked team
+    // that still has one
+    pub fn step(&mut self) -> Option<Reveal> {
+        let row = self.standings()
+            .into_iter()
+            .rev()
+            .find(|row| row.pending.iter().any(|p| *p))?;
+        let problem = row.pending.iter().position(|p| *p)?;
+
+        self.pending[row.index][problem] = false;
+        let accepted = matches!(self.entries[row.index].problems[problem], ProblemStatus::Solved { .. });
+
+        let reveal = Reveal {
+            team: row.index,
+            problem,
+            accepted,
+        };
+        self.last_reveal = Some(reveal);
+
+        Some(reveal)
+    }
+
+    pub fn finish(&mut self) {
+        while self.step().is_some() { }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard};
+    use crate::test_util::{solved, team};
+    use super::{Resolver, Reveal};
+
+    fn scoreboard() -> Scoreboard {
+        Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: Vec::new(),
+            entries: vec![
+                team("Late", vec![solved(1, 10 * 60), solved(2, 250 * 60)]),
+                team("Frozen", vec![solved(1, 20 * 60), ProblemStatus::Frozen { attempts: 1, pending: 2 }]),
+                team("Judging", vec![ProblemStatus::Incorrect, ProblemStatus::Pending { attempts: 1 }]),
+            ],
+            awards: AwardRules::default(),
+        }
+    }
+
+    fn order(resolver: &Resolver) -> Vec<usize> {
+        resolver.standings().iter().map(|row| row.index).collect()
+    }
+
+    #[test]
+    fn hides_submissions_after_the_freeze() {
+        let resolver = Resolver::new(&scoreboard(), 240);
+        let rows = resolver.standings();
+
+        assert_eq!(order(&resolver), vec![0, 1, 2]);
+        // solved at 4:10, not yet shown
+        assert_eq!(rows[0].visible.problems[1], ProblemStatus::Incorrect);
+        assert_eq!(rows[0].pending, vec![false, true]);
+        assert_eq!(rows[1].pending, vec![false, true]);
+        // still being judged when the board was captured, nothing to reveal
+        assert_eq!(rows[2].visible.problems[1], ProblemStatus::Pending { attempts: 1 });
+        assert_eq!(rows[2].pending, vec![false, false]);
+        assert!(!resolver.is_done());
+    }
+
+    #[test]
+    fn reveals_from_the_bottom() {
+        let mut resolver = Resolver::new(&scoreboard(), 240);
+
+        // frozen cells have no known verdict and stay unaccepted
+        assert_eq!(resolver.step(), Some(Reveal { team: 1, problem: 1, accepted: false }));
+        assert_eq!(resolver.standings()[1].visible.problems[1], ProblemStatus::Incorrect);
+        assert_eq!(order(&resolver), vec![0, 1, 2]);
+
+        assert_eq!(resolver.step(), Some(Reveal { team: 0, problem: 1, accepted: true }));
+        assert_eq!(resolver.standings()[0].visible.problems[1], solved(2, 250 * 60));
+        assert_eq!(resolver.last_reveal(), Some(Reveal { team: 0, problem: 1, accepted: true }));
+
+        assert_eq!(resolver.step(), None);
+        assert!(resolver.is_done());
+    }
+
+    #[test]
+    fn reveal_reorders() {
+        let sb = Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: Vec::new(),
+            entries: vec![
+                team("Leader", vec![solved(1, 30 * 60), ProblemStatus::Incorrect]),
+                team("Climber", vec![solved(1, 60 * 60), solved(1, 280 * 60)]),
+            ],
+            awards: AwardRules::default(),
+        };
+        let mut resolver = Resolver::new(&sb, 240);
+
+        assert_eq!(order(&resolver), vec![0, 1]);
+        resolver.finish();
+        assert_eq!(order(&resolver), vec![1, 0]);
+        assert!(resolver.is_done());
+    }
+}
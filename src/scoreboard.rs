@@ -1,13 +1,46 @@
-use quarve::state::{SetAction, Stateful};
-use quarve::util::marker::FalseMarker;
+use std::collections::HashSet;
+
+pub const CONTEST_MINUTES: usize = 300;
+// standard ICPC freeze, one hour before the end
+pub const FREEZE_MINUTES: usize = 240;
+
+// count times spread evenly strictly between from and to,
+// for tries whose submission time the source does not give
+pub fn spread(count: usize, from: usize, to: usize) -> impl Iterator<Item=usize> {
+    (0..count).map(move |k| from + (to - from) * (k + 1) / (count + 1))
+}
+
+// "A" for the first problem, continuing "Z", "AA", "AB" as in spreadsheets
+pub fn problem_label(index: usize) -> String {
+    let mut label = Vec::new();
+    let mut rest = index + 1;
+    while rest > 0 {
+        rest -= 1;
+        label.push(b'A' + (rest % 26) as u8);
+        rest /= 26;
+    }
+
+    label.iter().rev().map(|c| *c as char).collect()
+}
 
  // for a given team
 #[derive(Clone, PartialEq, Debug)]
 pub enum ProblemStatus {
     Incorrect,
+    // submitted and still being judged when the scoreboard was captured
+    Pending {
+        attempts: usize,
+    },
+    // submissions made after the freeze whose verdicts are hidden
+    Frozen {
+        // tries before the freeze
+        attempts: usize,
+        pending: usize,
+    },
     Solved {
         attempts: usize,
-        minutes: usize,
+        // since the start of the contest
+        seconds: usize,
         first_solve: bool,
     }
 }
@@ -15,20 +48,70 @@ pub enum ProblemStatus {
 #[derive(Clone, PartialEq, Debug)]
 pub struct TeamResult {
     pub team: String,
-    pub problems: Vec<ProblemStatus>
+    pub problems: Vec<ProblemStatus>,
+    // region the team won, as published for the world finals
+    pub regional_champion: Option<String>,
+    // university or other institution the team represents, if known
+    pub affiliation: Option<String>,
+}
+
+impl ProblemStatus {
+    // penalty minutes, if the problem was solved by elapsed_seconds
+    // scoring is in whole minutes as in ICPC
+    pub fn penalty(&self, elapsed_seconds: usize) -> Option<usize> {
+        match self {
+            ProblemStatus::Solved { attempts, seconds, .. } if *seconds <= elapsed_seconds => {
+                Some(seconds / 60 + attempts.saturating_sub(1) * 20)
+            }
+            _ => None
+        }
+    }
+
+    // the status as it would have been displayed at elapsed_seconds
+    pub fn visible_at(&self, elapsed_seconds: usize) -> ProblemStatus {
+        let shown = match self {
+            ProblemStatus::Incorrect => true,
+            // we only know these were pending once the board was captured
+            ProblemStatus::Pending { .. } => elapsed_seconds >= CONTEST_MINUTES * 60,
+            ProblemStatus::Frozen { attempts, pending } => {
+                let freeze = FREEZE_MINUTES * 60;
+                if elapsed_seconds < freeze {
+                    return ProblemStatus::Incorrect;
+                }
+
+                // tries after the freeze appear one by one over the frozen hour
+                let pending = spread(*pending, freeze, CONTEST_MINUTES * 60)
+                    .filter(|seconds| *seconds <= elapsed_seconds)
+                    .count();
+                if *attempts == 0 && pending == 0 {
+                    return ProblemStatus::Incorrect;
+                }
+
+                return ProblemStatus::Frozen { attempts: *attempts, pending };
+            }
+            ProblemStatus::Solved { seconds, .. } => elapsed_seconds >= *seconds,
+        };
+
+        if shown {
+            self.clone()
+        } else {
+            ProblemStatus::Incorrect
+        }
+    }
 }
 
 impl TeamResult {
-    pub fn score(&self, elapsed_minutes: usize) -> (isize, usize) {
+    // a team without a region or affiliation
+    pub fn new(team: String, problems: Vec<ProblemStatus>) -> TeamResult {
+        TeamResult { team, problems, regional_champion: None, affiliation: None }
+    }
+
+    pub fn score(&self, elapsed_seconds: usize) -> (isize, usize) {
         let (solved, time) = self.problems.iter()
             .fold((0, 0), |mut status, curr | {
-                if let ProblemStatus::Solved {
-                        attempts, minutes, ..
-                    } = curr {
-                    if *minutes <= elapsed_minutes {
-                        status.0 += 1;
-                        status.1 += minutes + (attempts - 1) * 20;
-                    }
+                if let Some(penalty) = curr.penalty(elapsed_seconds) {
+                    status.0 += 1;
+                    status.1 += penalty;
                 }
 
                 status
@@ -36,22 +119,329 @@ impl TeamResult {
 
         (-solved, time)
     }
+
+    // without affiliation data each team is its own institution,
+    // since names like "UCSD 1" and "UCSD 2" can't be told apart from "Team 1" and "Team 2"
+    pub fn institution(&self) -> &str {
+        self.affiliation.as_deref().unwrap_or(&self.team)
+    }
+}
+
+// number of teams receiving each medal, in rank order
+// e.g. the world finals usually award 4 of each
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct MedalLines {
+    pub gold: usize,
+    pub silver: usize,
+    pub bronze: usize,
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Award {
+    Gold,
+    Silver,
+    Bronze,
+    HonorableMention,
+}
+
+impl MedalLines {
+    // medal for a 0-indexed rank
+    pub fn medal(&self, rank: usize) -> Option<Award> {
+        if rank < self.gold {
+            Some(Award::Gold)
+        } else if rank < self.gold + self.silver {
+            Some(Award::Silver)
+        } else if rank < self.gold + self.silver + self.bronze {
+            Some(Award::Bronze)
+        } else {
+            None
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.gold + self.silver + self.bronze
+    }
+}
+
+// slots for the next stage of the contest
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Advancement {
+    pub slots: usize,
+    // only the best team of each institution can take a slot
+    pub per_institution: bool,
+}
+
+// where a team stands with respect to the advancement slots
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Qualification {
+    // holds the given 1-indexed slot
+    Advancing(usize),
+    // a better ranked team of the same institution already holds a slot
+    // or is ahead of it in the race for one
+    Blocked,
+    // number of eligible teams it would need to pass to take the last slot
+    Outside(usize),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct AwardRules {
+    pub medals: Option<MedalLines>,
+    pub advancement: Option<Advancement>,
+    // every team outside the medals that solved at least one problem
+    pub honorable_mention: bool,
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct Scoreboard {
+    // title of the contest, empty if the source does not provide it
+    pub name: String,
     pub num_problems: usize,
-    pub entries: Vec<TeamResult>
+    // titles of the problems, empty if the source does not provide them
+    pub problem_names: Vec<String>,
+    pub entries: Vec<TeamResult>,
+    pub awards: AwardRules,
 }
 
-// need a new typ
-#[derive(Clone, PartialEq)]
-pub enum ScoreboardOption {
-    Some(Scoreboard),
-    None
+impl Scoreboard {
+    pub fn problem_name(&self, problem: usize) -> Option<&str> {
+        self.problem_names.get(problem).map(|n| n.as_str())
+    }
+
+    // whether the source names institutions, without which
+    // advancing one team per institution can't be applied
+    pub fn has_affiliations(&self) -> bool {
+        self.entries.iter().any(|e| e.affiliation.is_some())
+    }
+
+    // one past the last problem that any team had touched by elapsed_seconds,
+    // so a live board does not give away how many problems are left
+    pub fn problems_seen(&self, elapsed_seconds: usize) -> usize {
+        self.entries.iter()
+            .filter_map(|e| e.problems.iter()
+                .rposition(|p| p.visible_at(elapsed_seconds) != ProblemStatus::Incorrect))
+            .max()
+            .map_or(0, |last| last + 1)
+    }
+
+    // indices into entries, best team first
+    pub fn ranking(&self, elapsed_seconds: usize) -> Vec<usize> {
+        let mut order = (0..self.entries.len()).collect::<Vec<_>>();
+        order.sort_by(|i1, i2| {
+            self.entries[*i1].score(elapsed_seconds).cmp(&self.entries[*i2].score(elapsed_seconds))
+        });
+
+        order
+    }
+
+    // award held by each position of ranking(elapsed_seconds)
+    pub fn awards_at(&self, elapsed_seconds: usize) -> Vec<Option<Award>> {
+        self.ranking(elapsed_seconds)
+            .into_iter()
+            .enumerate()
+            .map(|(rank, team)| {
+                let medal = self.awards.medals.and_then(|m| m.medal(rank));
+                let solved_any = self.entries[team].score(elapsed_seconds).0 < 0;
+
+                match medal {
+                    None if self.awards.honorable_mention && solved_any => Some(Award::HonorableMention),
+                    medal => medal
+                }
+            })
+            .collect()
+    }
+
+    // qualification of each position of ranking(elapsed_seconds),
+    // None everywhere if the contest has no advancement rules
+    pub fn qualification_at(&self, elapsed_seconds: usize) -> Vec<Option<Qualification>> {
+        let ranking = self.ranking(elapsed_seconds);
+        let Some(advancement) = self.awards.advancement else {
+            return vec![None; ranking.len()];
+        };
+
+        let mut eligible = 0;
+        let mut institutions = HashSet::new();
+        ranking.into_iter()
+            .map(|team| {
+                let entry = &self.entries[team];
+                if advancement.per_institution && !institutions.insert(entry.institution()) {
+                    return Some(Qualification::Blocked);
+                }
+
+                eligible += 1;
+                if eligible <= advancement.slots {
+                    Some(Qualification::Advancing(eligible))
+                } else {
+                    Some(Qualification::Outside(eligible - advancement.slots))
+                }
+            })
+            .collect()
+    }
+
+    // strips everything that was not known live: entries are ordered by name
+    // rather than by final standing (which would otherwise break ties at minute 0),
+    // and first solves are derived from solve times alone
+    pub fn without_spoilers(&self) -> Scoreboard {
+        let mut entries = self.entries.clone();
+        entries.sort_by(|t1, t2| t1.team.cmp(&t2.team));
+
+        for i in 0..self.num_problems {
+            let first = entries.iter()
+                .filter_map(|e| match e.problems.get(i) {
+                    Some(ProblemStatus::Solved { seconds, .. }) => Some(*seconds),
+                    _ => None
+                })
+                .min();
+
+            for e in entries.iter_mut() {
+                if let Some(ProblemStatus::Solved { seconds, first_solve, .. }) = e.problems.get_mut(i) {
+                    *first_solve = Some(*seconds) == first;
+                }
+            }
+        }
+
+        Scoreboard {
+            name: self.name.clone(),
+            num_problems: self.num_problems,
+            problem_names: self.problem_names.clone(),
+            entries,
+            awards: self.awards,
+        }
+    }
 }
 
-impl Stateful for ScoreboardOption {
-    type Action = SetAction<ScoreboardOption>;
-    type HasInnerStores = FalseMarker;
-}
\ No newline at end of file
+#[cfg(test)]
+mod tests {
+    use crate::test_util::{solved, team};
+    use super::{problem_label, spread, Advancement, Award, AwardRules, MedalLines, ProblemStatus, Qualification, Scoreboard, TeamResult};
+
+    fn scoreboard(entries: Vec<TeamResult>, awards: AwardRules) -> Scoreboard {
+        Scoreboard { name: String::new(), num_problems: 2, problem_names: Vec::new(), entries, awards }
+    }
+
+    #[test]
+    fn labels() {
+        assert_eq!(problem_label(0), "A");
+        assert_eq!(problem_label(25), "Z");
+        assert_eq!(problem_label(26), "AA");
+        assert_eq!(problem_label(27), "AB");
+        assert_eq!(problem_label(26 * 27), "AAA");
+    }
+
+    #[test]
+    fn spreading() {
+        assert_eq!(spread(2, 0, 60).collect::<Vec<_>>(), vec![20, 40]);
+        assert_eq!(spread(1, 240, 300).collect::<Vec<_>>(), vec![270]);
+        assert_eq!(spread(0, 0, 60).count(), 0);
+    }
+
+    #[test]
+    fn frozen() {
+        let frozen = ProblemStatus::Frozen { attempts: 1, pending: 2 };
+        assert_eq!(frozen.visible_at(240 * 60 - 1), ProblemStatus::Incorrect);
+        // the tries made during the frozen hour have not happened yet
+        assert_eq!(frozen.visible_at(240 * 60), ProblemStatus::Frozen { attempts: 1, pending: 0 });
+        assert_eq!(frozen.visible_at(270 * 60), ProblemStatus::Frozen { attempts: 1, pending: 1 });
+        assert_eq!(frozen.visible_at(299 * 60), ProblemStatus::Frozen { attempts: 1, pending: 2 });
+
+        // a problem first tried after the freeze stays blank until then
+        let late = ProblemStatus::Frozen { attempts: 0, pending: 1 };
+        assert_eq!(late.visible_at(240 * 60), ProblemStatus::Incorrect);
+        assert_eq!(late.visible_at(269 * 60), ProblemStatus::Incorrect);
+        assert_eq!(late.visible_at(270 * 60), ProblemStatus::Frozen { attempts: 0, pending: 1 });
+        assert_eq!(late.visible_at(299 * 60), ProblemStatus::Frozen { attempts: 0, pending: 1 });
+    }
+
+    #[test]
+    fn score() {
+        let t = team("Team", vec![solved(1, 30 * 60), solved(3, 100 * 60), ProblemStatus::Pending { attempts: 4 }]);
+
+        assert_eq!(t.score(0), (0, 0));
+        // solved in the 30th minute, on the first try
+        assert_eq!(t.score(30 * 60), (-1, 30));
+        assert_eq!(t.score(100 * 60 - 1), (-1, 30));
+        // two rejected tries add 40 minutes
+        assert_eq!(t.score(100 * 60), (-2, 30 + 100 + 40));
+        assert_eq!(t.score(usize::MAX), (-2, 170));
+    }
+
+    #[test]
+    fn score_rounds_down_to_minutes() {
+        let t = team("Team", vec![ProblemStatus::Solved { attempts: 1, seconds: 59, first_solve: false }]);
+        assert_eq!(t.score(59), (-1, 0));
+    }
+
+    #[test]
+    fn score_without_attempts() {
+        // some sources omit the try count of solved problems
+        let t = team("Team", vec![solved(0, 10 * 60)]);
+        assert_eq!(t.score(10 * 60), (-1, 10));
+    }
+
+    #[test]
+    fn problems_seen() {
+        let sb = Scoreboard {
+            name: String::new(),
+            num_problems: 4,
+            ..scoreboard(vec![
+                team("A", vec![solved(1, 10 * 60), ProblemStatus::Incorrect, solved(2, 90 * 60), ProblemStatus::Incorrect]),
+                team("B", vec![ProblemStatus::Incorrect, solved(1, 30 * 60)]),
+            ], AwardRules::default())
+        };
+
+        assert_eq!(sb.problems_seen(0), 0);
+        assert_eq!(sb.problems_seen(10 * 60), 1);
+        assert_eq!(sb.problems_seen(30 * 60), 2);
+        assert_eq!(sb.problems_seen(5 * 3600), 3);
+    }
+
+    #[test]
+    fn institutions() {
+        // teams that only look alike are kept apart
+        assert_eq!(team("Team 2", Vec::new()).institution(), "Team 2");
+        assert_eq!(team("Rocket (Prague)", Vec::new()).institution(), "Rocket (Prague)");
+
+        let mut t = team("UCSD 2", Vec::new());
+        t.affiliation = Some("University of California San Diego".to_string());
+        assert_eq!(t.institution(), "University of California San Diego");
+    }
+
+    #[test]
+    fn awards() {
+        let sb = scoreboard(vec![
+            team("C", Vec::new()),
+            team("A", vec![solved(1, 10 * 60), solved(1, 20 * 60)]),
+            team("B", vec![solved(1, 10 * 60)]),
+        ], AwardRules {
+            medals: Some(MedalLines { gold: 1, silver: 0, bronze: 0 }),
+            advancement: None,
+            honorable_mention: true,
+        });
+
+        assert_eq!(sb.ranking(5 * 3600), vec![1, 2, 0]);
+        assert_eq!(sb.awards_at(5 * 3600), vec![Some(Award::Gold), Some(Award::HonorableMention), None]);
+    }
+
+    #[test]
+    fn qualification() {
+        let ucsd = |name, problems| TeamResult { affiliation: Some("UCSD".to_string()), ..team(name, problems) };
+        let sb = scoreboard(vec![
+            ucsd("UCSD 1", vec![solved(1, 10 * 60), solved(1, 20 * 60)]),
+            ucsd("UCSD 2", vec![solved(1, 10 * 60)]),
+            // same name, but no affiliation to say it is the same school
+            team("UCSD 2", vec![solved(1, 30 * 60)]),
+            team("CMU", Vec::new()),
+        ], AwardRules {
+            medals: None,
+            advancement: Some(Advancement { slots: 2, per_institution: true }),
+            honorable_mention: false,
+        });
+
+        assert_eq!(sb.qualification_at(5 * 3600), vec![
+            Some(Qualification::Advancing(1)),
+            Some(Qualification::Blocked),
+            Some(Qualification::Advancing(2)),
+            Some(Qualification::Outside(1)),
+        ]);
+        assert_eq!(scoreboard(sb.entries.clone(), AwardRules::default()).qualification_at(0), vec![None; 4]);
+    }
+}
@@ -0,0 +1,446 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use serde::Serialize;
+use crate::clics::ContestApi;
+use crate::export::ndjson;
+use crate::replay::{format_clock, replay_clock};
+use crate::scoreboard::{problem_label, Award, ProblemStatus, Scoreboard, CONTEST_MINUTES};
+
+// how often the html page reloads itself
+const REFRESH_SECONDS: usize = 5;
+// the contest api asks for something at least every 120 seconds on the event feed
+const KEEPALIVE_SECONDS: usize = 100;
+
+#[derive(Serialize)]
+struct Problem {
+    label: String,
+    name: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Cell {
+    // "solved", "pending", "frozen" or "unsolved"
+    status: &'static str,
+    attempts: usize,
+    // submissions after the freeze
+    pending: usize,
+    // contest minute of the accepted submission
+    minute: Option<usize>,
+    first_solve: bool,
+}
+
+#[derive(Serialize)]
+struct Row {
+    // 1-indexed
+    rank: usize,
+    team: String,
+    affiliation: Option<String>,
+    solved: usize,
+    penalty: usize,
+    award: Option<&'static str>,
+    problems: Vec<Cell>,
+}
+
+#[derive(Serialize)]
+struct Standings {
+    elapsed_seconds: usize,
+    // seconds until the replay starts, 0 once it has
+    starts_in: usize,
+    contest_seconds: usize,
+    problems: Vec<Problem>,
+    rows: Vec<Row>,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: String) -> Response {
+        Response { status: 200, content_type, body }
+    }
+
+    fn not_found() -> Response {
+        Response { status: 404, content_type: "text/plain", body: "Not found".to_string() }
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            500 => "Internal Server Error",
+            _ => ""
+        }
+    }
+}
+
+fn award_name(award: Award) -> &'static str {
+    match award {
+        Award::Gold => "gold",
+        Award::Silver => "silver",
+        Award::Bronze => "bronze",
+        Award::HonorableMention => "honorable mention",
+    }
+}
+
+fn cell(status: ProblemStatus) -> Cell {
+    match status {
+        ProblemStatus::Incorrect => Cell { status: "unsolved", attempts: 0, pending: 0, minute: None, first_solve: false },
+        ProblemStatus::Pending { attempts } => Cell { status: "pending", attempts, pending: 0, minute: None, first_solve: false },
+        ProblemStatus::Frozen { attempts, pending } => Cell { status: "frozen", attempts, pending, minute: None, first_solve: false },
+        ProblemStatus::Solved { attempts, seconds, first_solve } => Cell {
+            status: "solved",
+            attempts,
+            pending: 0,
+            minute: Some(seconds / 60),
+            first_solve,
+        },
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Serves the standings of a replay over http, as json for other tools,
+// as a page that refreshes itself for a projector, and as a CLICS contest api under /api.
+// The clock is the same as the app's, so a server started with the
+// session's start time shows what the app shows
+pub struct Server {
+    pub sb: Scoreboard,
+    // contest name in the api, which also gives its id
+    pub name: String,
+    // wall-clock time at which the replay is at minute 0
+    pub start: SystemTime,
+}
+
+impl Server {
+    fn api(&self) -> ContestApi<'_> {
+        ContestApi { sb: &self.sb, name: &self.name, start: self.start }
+    }
+
+    fn standings(&self, now: SystemTime) -> Standings {
+        let (elapsed, starts_in) = replay_clock(self.start, now);
+        let awards = self.sb.awards_at(elapsed);
+
+        let rows = self.sb.ranking(elapsed)
+            .into_iter()
+            .enumerate()
+            .map(|(rank, team)| {
+                let entry = &self.sb.entries[team];
+                let (solved, penalty) = entry.score(elapsed);
+
+                Row {
+                    rank: rank + 1,
+                    team: entry.team.clone(),
+                    affiliation: entry.affiliation.clone(),
+                    solved: (-solved) as usize,
+                    penalty,
+                    award: awards[rank].map(award_name),
+                    problems: entry.problems.iter()
+                        .map(|p| cell(p.visible_at(elapsed)))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Standings {
+            elapsed_seconds: elapsed,
+            starts_in,
+            contest_seconds: CONTEST_MINUTES * 60,
+            problems: (0..self.sb.num_problems)
+                .map(|i| Problem {
+                    label: problem_label(i),
+                    name: self.sb.problem_name(i).map(|n| n.to_string()),
+                })
+                .collect(),
+            rows,
+        }
+    }
+
+    fn html(&self, now: SystemTime) -> String {
+        let standings = self.standings(now);
+
+        let clock = if standings.starts_in > 0 {
+            format!("Starts in {}", format_clock(standings.starts_in))
+        } else {
+            format!("Time {}", format_clock(standings.elapsed_seconds))
+        };
+
+        let headers = standings.problems.iter()
+            .map(|p| format!("<th title=\"{}\">{}</th>", escape(p.name.as_deref().unwrap_or("")), p.label))
+            .collect::<String>();
+
+        let rows = standings.rows.iter()
+            .map(|row| {
+                let cells = row.problems.iter()
+                    .map(|c| {
+                        let class = if c.first_solve { "first" } else { c.status };
+                        // as in the app
+                        let content = match c.status {
+                            "solved" => format!("{}<br><small>{} min</small>", c.attempts, c.minute.unwrap_or(0)),
+                            "pending" => format!("...<br><small>{} tries</small>", c.attempts),
+                            "frozen" => format!("?<br><small>{} + {}</small>", c.attempts, c.pending),
+                            _ => "".to_string()
+                        };
+
+                        format!("<td class=\"{}\">{}</td>", class, content)
+                    })
+                    .collect::<String>();
+
+                format!(
+                    "<tr class=\"{}\"><td>{}</td><td>{}</td><td>{}</td><td>{}</td>{}</tr>\n",
+                    row.award.unwrap_or("").replace(' ', "-"), row.rank, escape(&row.team), row.solved, row.penalty, cells
+                )
+            })
+            .collect::<String>();
+
+        format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<meta http-equiv="refresh" content="{}">
+<title>Scoreboard</title>
+<style>
+body {{ font-family: sans-serif; }}
+table {{ border-collapse: collapse; }}
+td, th {{ border: 1px solid #ccc; padding: 4px 8px; text-align: center; }}
+td:nth-child(2) {{ text-align: left; }}
+small {{ color: #555; }}
+.solved {{ background: #54e74d; }}
+.first {{ background: #209f17; }}
+.pending {{ background: #90caf9; }}
+.frozen {{ background: #ffcc00; }}
+.gold td:first-child {{ background: #fd4; }}
+.silver td:first-child {{ background: #ccc; }}
+.bronze td:first-child {{ background: #d95; }}
+</style>
+</head>
+<body>
+<h2>{}</h2>
+<table>
+<tr><th>Rank</th><th>Team</th><th>Solved</th><th>Time</th>{}</tr>
+{}</table>
+</body>
+</html>
+"#, REFRESH_SECONDS, clock, headers, rows)
+    }
+
+    // the query string is ignored, and the event feed is answered with the events so far
+    pub fn respond(&self, path: &str, now: SystemTime) -> Response {
+        let path = path.split('?').next().unwrap_or("");
+
+        match path {
+            "/" | "/index.html" => Response::ok("text/html; charset=utf-8", self.html(now)),
+            "/standings.json" => match serde_json::to_string(&self.standings(now)) {
+                Ok(json) => Response::ok("application/json", json),
+                Err(e) => Response { status: 500, content_type: "text/plain", body: e.to_string() }
+            },
+            _ if path == self.api().event_feed_path() => Response::ok("application/x-ndjson", ndjson(&self.api().events(now))),
+            _ => match self.api().get(path, now) {
+                Some(json) => Response::ok("application/json", json.to_string()),
+                None => Response::not_found()
+            }
+        }
+    }
+
+    // Keeps the connection open, sending each event once the replay reaches it,
+    // until the end of the contest
+    fn stream_events(&self, mut stream: &TcpStream) -> io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n"
+        )?;
+
+        let api = self.api();
+        let mut sent = 0;
+        let mut idle = 0;
+        loop {
+            let now = SystemTime::now();
+            let events = api.events(now);
+
+            if events.len() > sent {
+                for event in &events[sent..] {
+                    writeln!(stream, "{}", event)?;
+                }
+                sent = events.len();
+                idle = 0;
+            } else if idle >= KEEPALIVE_SECONDS {
+                writeln!(stream)?;
+                idle = 0;
+            }
+            stream.flush()?;
+
+            if api.finished(now) {
+                return Ok(());
+            }
+
+            thread::sleep(Duration::from_secs(1));
+            idle += 1;
+        }
+    }
+
+    fn handle(&self, stream: TcpStream) -> io::Result<()> {
+        // a client that never finishes its request should not hold on to a thread
+        stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+        let mut reader = BufReader::new(&stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line)?;
+        // headers are not needed
+        let mut header = String::new();
+        while reader.read_line(&mut header)? > 0 && header.trim() != "" {
+            header.clear();
+        }
+
+        let mut parts = request_line.split_whitespace();
+        let (method, target) = (parts.next(), parts.next());
+
+        if let (Some("GET"), Some(target)) = (method, target) {
+            let (path, query) = target.split_once('?').unwrap_or((target, ""));
+            if path == self.api().event_feed_path() && !query.split('&').any(|q| q == "stream=false") {
+                return self.stream_events(&stream);
+            }
+        }
+
+        let response = match (method, target) {
+            (Some("GET"), Some(path)) => self.respond(path, SystemTime::now()),
+            _ => Response { status: 400, content_type: "text/plain", body: "Only GET requests are supported".to_string() }
+        };
+
+        let mut stream = &stream;
+        write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nConnection: close\r\n\r\n",
+            response.status, response.reason(), response.content_type, response.body.len()
+        )?;
+        stream.write_all(response.body.as_bytes())?;
+        stream.flush()
+    }
+
+    // blocks, answering each connection on its own thread
+    pub fn run(&self, addr: impl ToSocketAddrs) -> io::Result<()> {
+        let listener = TcpListener::bind(addr)?;
+
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else {
+                    continue;
+                };
+
+                // one failed connection does not concern the others
+                scope.spawn(move || {
+                    let _ = self.handle(stream);
+                });
+            }
+        });
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+    use serde_json::Value;
+    use crate::scoreboard::{AwardRules, MedalLines, ProblemStatus, Scoreboard};
+    use crate::test_util::{first_solve, solved, team};
+    use super::Server;
+
+    fn server() -> Server {
+        Server {
+            name: "Replay".to_string(),
+            sb: Scoreboard {
+                name: String::new(),
+                num_problems: 2,
+                problem_names: vec!["Apples".to_string(), "<Bananas>".to_string()],
+                entries: vec![
+                    team("Late", vec![
+                        solved(2, 100 * 60),
+                        ProblemStatus::Frozen { attempts: 1, pending: 2 },
+                    ]),
+                    team("Early & Co", vec![
+                        first_solve(1, 10 * 60),
+                        ProblemStatus::Incorrect,
+                    ]),
+                ],
+                awards: AwardRules { medals: Some(MedalLines { gold: 1, silver: 0, bronze: 0 }), advancement: None, honorable_mention: false },
+            },
+            start: UNIX_EPOCH + Duration::from_secs(1_000_000),
+        }
+    }
+
+    fn json(server: &Server, minutes: u64) -> Value {
+        let response = server.respond("/standings.json?t=1", server.start + Duration::from_secs(minutes * 60));
+        assert_eq!(response.status, 200);
+
+        serde_json::from_str(&response.body).unwrap()
+    }
+
+    #[test]
+    fn standings_follow_the_clock() {
+        let server = server();
+
+        let early = json(&server, 30);
+        assert_eq!(early["elapsed_seconds"], 30 * 60);
+        assert_eq!(early["rows"][0]["team"], "Early & Co");
+        assert_eq!(early["rows"][0]["award"], "gold");
+        assert_eq!(early["rows"][1]["team"], "Late");
+        // not solved yet, and the freeze has not started
+        assert_eq!(early["rows"][1]["problems"][0]["status"], "unsolved");
+        assert_eq!(early["rows"][1]["problems"][1]["status"], "unsolved");
+
+        let end = json(&server, 300);
+        assert_eq!(end["rows"][0]["team"], "Early & Co");
+        assert_eq!(end["rows"][1]["solved"], 1);
+        assert_eq!(end["rows"][1]["penalty"], 120);
+        assert_eq!(end["rows"][1]["problems"][0]["minute"], 100);
+        assert_eq!(end["rows"][1]["problems"][1]["status"], "frozen");
+        assert_eq!(end["rows"][1]["problems"][1]["pending"], 2);
+    }
+
+    #[test]
+    fn countdown() {
+        let server = server();
+        let response = server.respond("/standings.json", server.start - Duration::from_millis(89_500));
+        let standings: Value = serde_json::from_str(&response.body).unwrap();
+
+        assert_eq!(standings["elapsed_seconds"], 0);
+        assert_eq!(standings["starts_in"], 90);
+    }
+
+    #[test]
+    fn page() {
+        let server = server();
+        let response = server.respond("/", server.start + Duration::from_secs(3600));
+
+        assert_eq!(response.status, 200);
+        assert!(response.body.contains("Time 01:00:00"));
+        assert!(response.body.contains("Early &amp; Co"));
+        assert!(response.body.contains("title=\"&lt;Bananas&gt;\""));
+        assert_eq!(server.respond("/missing", server.start).status, 404);
+    }
+
+    #[test]
+    fn contest_api() {
+        let server = server();
+        let now = server.start + Duration::from_secs(3600);
+
+        let teams = server.respond("/api/contests/replay/teams", now);
+        assert_eq!(teams.content_type, "application/json");
+        assert_eq!(serde_json::from_str::<Value>(&teams.body).unwrap()[1]["name"], "Early & Co");
+
+        let feed = server.respond("/api/contests/replay/event-feed?stream=false", now);
+        assert_eq!(feed.content_type, "application/x-ndjson");
+        assert!(feed.body.lines().all(|line| serde_json::from_str::<Value>(line).is_ok()));
+        assert_eq!(server.respond("/api/contests/replay/missing", now).status, 404);
+    }
+}
@@ -0,0 +1,98 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use quarve::resource::local_storage;
+use quarve::state::{SetAction, Stateful};
+use quarve::util::marker::FalseMarker;
+use icpc_scoreboard::scoreboard::CONTEST_MINUTES;
+
+// a replay that should survive restarting the app,
+// e.g. a practice session where everyone starts at 10:00
+#[derive(Clone, PartialEq, Debug)]
+pub struct Session {
+    pub contest_type: String,
+    pub url: String,
+    // unix seconds at which the replay is at minute 0
+    pub start: u64,
+}
+
+// the session a window is replaying, if it has started one
+#[derive(Clone, PartialEq)]
+pub struct WindowSession(pub Option<Session>);
+
+impl Stateful for WindowSession {
+    type Action = SetAction<WindowSession>;
+    type HasInnerStores = FalseMarker;
+}
+
+fn path() -> PathBuf {
+    local_storage().join("sessions.txt")
+}
+
+fn write_all(sessions: &[Session]) -> io::Result<()> {
+    let path = path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = sessions.iter()
+        .map(|session| format!("{}\t{}\t{}\n", session.contest_type, session.url, session.start))
+        .collect::<String>();
+    fs::write(path, content)
+}
+
+impl Session {
+    // one tab separated session per line, each window saves its own
+    pub fn load_all() -> Vec<Session> {
+        let Ok(content) = fs::read_to_string(path()) else {
+            return Vec::new();
+        };
+
+        content.lines()
+            .filter_map(|line| {
+                let mut fields = line.split('\t');
+                Some(Session {
+                    contest_type: fields.next()?.to_string(),
+                    url: fields.next()?.to_string(),
+                    start: fields.next()?.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+
+    // sessions that should be resumed, one window each
+    pub fn load_active(now: SystemTime) -> Vec<Session> {
+        Session::load_all()
+            .into_iter()
+            .filter(|session| session.is_active(now))
+            .collect()
+    }
+
+    // replaces any session for the same url and forgets the ones that have ended
+    pub fn save(&self) -> io::Result<()> {
+        let mut sessions = Session::load_active(SystemTime::now());
+        sessions.retain(|session| session.url != self.url);
+        sessions.push(self.clone());
+
+        write_all(&sessions)
+    }
+
+    // forgets the session for url, once its window closes or moves on to another contest
+    pub fn remove(url: &str) -> io::Result<()> {
+        let mut sessions = Session::load_active(SystemTime::now());
+        sessions.retain(|session| session.url != url);
+
+        write_all(&sessions)
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.start)
+    }
+
+    // whether the replay has not yet reached the end of the contest
+    pub fn is_active(&self, now: SystemTime) -> bool {
+        let end = self.start_time() + Duration::from_secs(CONTEST_MINUTES as u64 * 60);
+        now < end
+    }
+}
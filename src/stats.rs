@@ -0,0 +1,185 @@
+use crate::scoreboard::{ProblemStatus, Scoreboard};
+
+pub const CHART_BUCKET_MINUTES: usize = 10;
+
+// Note that the parsers only record attempts for solved, pending and frozen problems,
+// so rejected submissions of teams that never solved a problem are not counted
+#[derive(Clone, PartialEq, Debug)]
+pub struct ProblemStats {
+    pub solved: usize,
+    pub attempted: usize,
+    pub submissions: usize,
+    // team name and seconds
+    pub first_solve: Option<(String, usize)>,
+}
+
+impl ProblemStats {
+    pub fn average_attempts(&self) -> Option<f64> {
+        if self.attempted == 0 {
+            None
+        } else {
+            Some(self.submissions as f64 / self.attempted as f64)
+        }
+    }
+
+    pub fn acceptance_rate(&self) -> Option<f64> {
+        if self.submissions == 0 {
+            None
+        } else {
+            Some(self.solved as f64 / self.submissions as f64)
+        }
+    }
+}
+
+pub fn problem_stats(sb: &Scoreboard, elapsed_seconds: usize) -> Vec<ProblemStats> {
+    (0..sb.num_problems)
+        .map(|i| {
+            let mut stats = ProblemStats {
+                solved: 0,
+                attempted: 0,
+                submissions: 0,
+                first_solve: None,
+            };
+
+            for entry in &sb.entries {
+                let Some(status) = entry.problems.get(i) else {
+                    continue;
+                };
+
+                match status.visible_at(elapsed_seconds) {
+                    ProblemStatus::Incorrect => {}
+                    ProblemStatus::Pending { attempts } => {
+                        stats.attempted += 1;
+                        stats.submissions += attempts;
+                    }
+                    ProblemStatus::Frozen { attempts, pending } => {
+                        stats.attempted += 1;
+                        stats.submissions += attempts + pending;
+                    }
+                    ProblemStatus::Solved { attempts, seconds, .. } => {
+                        stats.solved += 1;
+                        stats.attempted += 1;
+                        stats.submissions += attempts;
+
+                        if stats.first_solve.as_ref().is_none_or(|(_, t)| seconds < *t) {
+                            stats.first_solve = Some((entry.team.clone(), seconds));
+                        }
+                    }
+                }
+            }
+
+            stats
+        })
+        .collect()
+}
+
+// number of buckets that have started by elapsed_seconds, at least one
+pub fn bucket_count(elapsed_seconds: usize, bucket_minutes: usize) -> usize {
+    elapsed_seconds.div_ceil(bucket_minutes * 60).max(1)
+}
+
+// end (in seconds) of every bucket that has started by elapsed_seconds
+fn bucket_ends(elapsed_seconds: usize, bucket_minutes: usize) -> impl Iterator<Item=usize> {
+    let bucket_seconds = bucket_minutes * 60;
+    (0..bucket_count(elapsed_seconds, bucket_minutes))
+        .map(move |b| ((b + 1) * bucket_seconds).min(elapsed_seconds))
+}
+
+// cumulative number of solves of each problem at the end of every bucket
+pub fn solves_over_time(sb: &Scoreboard, elapsed_seconds: usize, bucket_minutes: usize) -> Vec<Vec<usize>> {
+    (0..sb.num_problems)
+        .map(|i| {
+            bucket_ends(elapsed_seconds, bucket_minutes)
+                .map(|end| {
+                    sb.entries.iter()
+                        .filter(|e| matches!(
+                            e.problems.get(i),
+                            Some(ProblemStatus::Solved { seconds, .. }) if *seconds <= end
+                        ))
+                        .count()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// 1-indexed rank of the given team at the end of every bucket
+pub fn rank_over_time(sb: &Scoreboard, team: usize, elapsed_seconds: usize, bucket_minutes: usize) -> Vec<usize> {
+    bucket_ends(elapsed_seconds, bucket_minutes)
+        .map(|end| {
+            sb.ranking(end)
+                .iter()
+                .position(|i| *i == team)
+                .map_or(0, |r| r + 1)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::scoreboard::{AwardRules, ProblemStatus, Scoreboard};
+    use crate::test_util::{first_solve, solved, team};
+    use super::{bucket_count, problem_stats, rank_over_time, solves_over_time, ProblemStats};
+
+    fn scoreboard() -> Scoreboard {
+        Scoreboard {
+            name: String::new(),
+            num_problems: 2,
+            problem_names: Vec::new(),
+            entries: vec![
+                team("One", vec![first_solve(1, 5 * 60), solved(3, 25 * 60)]),
+                team("Two", vec![solved(2, 15 * 60), ProblemStatus::Pending { attempts: 2 }]),
+                team("Three", vec![ProblemStatus::Frozen { attempts: 1, pending: 1 }, first_solve(1, 12 * 60)]),
+            ],
+            awards: AwardRules::default(),
+        }
+    }
+
+    #[test]
+    fn buckets() {
+        // the whole contest in 10 minute buckets
+        assert_eq!(bucket_count(300 * 60, 10), 30);
+        assert_eq!(bucket_count(300 * 60 + 1, 10), 31);
+        assert_eq!(bucket_count(10 * 60, 10), 1);
+        assert_eq!(bucket_count(0, 10), 1);
+    }
+
+    #[test]
+    fn problems() {
+        let sb = scoreboard();
+
+        assert_eq!(problem_stats(&sb, 10 * 60), vec![
+            ProblemStats { solved: 1, attempted: 1, submissions: 1, first_solve: Some(("One".to_string(), 5 * 60)) },
+            ProblemStats { solved: 0, attempted: 0, submissions: 0, first_solve: None },
+        ]);
+
+        let stats = problem_stats(&sb, 300 * 60);
+        assert_eq!(stats[0], ProblemStats { solved: 2, attempted: 3, submissions: 5, first_solve: Some(("One".to_string(), 5 * 60)) });
+        assert_eq!(stats[1], ProblemStats { solved: 2, attempted: 3, submissions: 6, first_solve: Some(("Three".to_string(), 12 * 60)) });
+        assert_eq!(stats[0].acceptance_rate(), Some(2.0 / 5.0));
+        assert_eq!(stats[1].average_attempts(), Some(2.0));
+        assert_eq!(problem_stats(&sb, 0)[1].average_attempts(), None);
+    }
+
+    #[test]
+    fn solves() {
+        let sb = scoreboard();
+
+        assert_eq!(solves_over_time(&sb, 30 * 60, 10), vec![vec![1, 2, 2], vec![0, 1, 2]]);
+        // the last bucket ends with the replay
+        assert_eq!(solves_over_time(&sb, 12 * 60, 10), vec![vec![1, 1], vec![0, 1]]);
+        assert_eq!(solves_over_time(&sb, 300 * 60, 10)[0].len(), 30);
+    }
+
+    #[test]
+    fn ranks() {
+        let sb = scoreboard();
+
+        // first from the 5th minute on
+        assert_eq!(rank_over_time(&sb, 0, 30 * 60, 10), vec![1, 1, 1]);
+        // tied with nothing solved after 10 minutes, then "Three" solves at 0:12
+        // and "Two" at 0:15 with a rejected try
+        assert_eq!(rank_over_time(&sb, 1, 30 * 60, 10), vec![2, 3, 3]);
+        assert_eq!(rank_over_time(&sb, 2, 30 * 60, 10), vec![3, 2, 2]);
+    }
+}
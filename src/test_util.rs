@@ -0,0 +1,15 @@
+// factories shared by the tests, times are in seconds
+use crate::scoreboard::{ProblemStatus, TeamResult};
+
+pub fn team(name: &str, problems: Vec<ProblemStatus>) -> TeamResult {
+    TeamResult::new(name.to_string(), problems)
+}
+
+pub fn solved(attempts: usize, seconds: usize) -> ProblemStatus {
+    ProblemStatus::Solved { attempts, seconds, first_solve: false }
+}
+
+// solved before any other team
+pub fn first_solve(attempts: usize, seconds: usize) -> ProblemStatus {
+    ProblemStatus::Solved { attempts, seconds, first_solve: true }
+}
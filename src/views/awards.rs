@@ -0,0 +1,199 @@
+use quarve::prelude::*;
+use quarve::state::{Filterless, SetAction, Stateful, Store};
+use quarve::state::SetAction::Set;
+use quarve::view::control::Button;
+use quarve::view::modal::{MessageBox, MessageBoxButton};
+use quarve::view::text::{Text, TextField, TextModifier};
+use quarve::util::marker::FalseMarker;
+use quarve::view::util::Color;
+use icpc_scoreboard::scoreboard::{Advancement, Award, AwardRules, MedalLines, Qualification, Scoreboard, TeamResult};
+use crate::IVP;
+
+// award rules as app state, kept out of the library
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct Rules(pub AwardRules);
+
+impl Stateful for Rules {
+    type Action = SetAction<Rules>;
+    type HasInnerStores = FalseMarker;
+}
+
+// a row of the replay scoreboard at the current time
+#[derive(Clone, PartialEq)]
+pub(super) struct Standing {
+    pub rank: usize,
+    // index into the scoreboard entries
+    pub team: usize,
+    pub result: TeamResult,
+    pub award: Option<Award>,
+    pub qualification: Option<Qualification>,
+    // line drawn below the row, if it is the last one of a medal or the advancement cut
+    pub line: Option<Color>,
+}
+
+pub(super) fn award_color(award: Option<Award>) -> Color {
+    match award {
+        Some(Award::Gold) => rgb(255, 215, 0),
+        Some(Award::Silver) => rgb(192, 192, 192),
+        Some(Award::Bronze) => rgb(205, 127, 50),
+        Some(Award::HonorableMention) => rgb(187, 222, 251),
+        None => CLEAR
+    }
+}
+
+pub(super) fn standings(sb: &Scoreboard, elapsed_seconds: usize) -> Vec<Standing> {
+    let awards = sb.awards_at(elapsed_seconds);
+    let qualification = sb.qualification_at(elapsed_seconds);
+    let last_advancing = qualification.iter()
+        .rposition(|q| matches!(q, Some(Qualification::Advancing(_))));
+    let medal_ends = sb.awards.medals
+        .map(|m| [m.gold, m.gold + m.silver, m.total()])
+        .unwrap_or_default();
+
+    sb.ranking(elapsed_seconds)
+        .into_iter()
+        .enumerate()
+        .map(|(rank, team)| {
+            let line = if Some(rank) == last_advancing {
+                Some(RED)
+            } else if medal_ends.contains(&(rank + 1)) {
+                Some(DARK_GRAY)
+            } else {
+                None
+            };
+
+            Standing {
+                rank,
+                team,
+                result: sb.entries[team].clone(),
+                award: awards[rank],
+                qualification: qualification[rank],
+                line,
+            }
+        })
+        .collect()
+}
+
+// short label for the advancement column
+pub(super) fn qualification_label(qualification: Option<Qualification>) -> String {
+    match qualification {
+        Some(Qualification::Advancing(slot)) => format!("Slot {}", slot),
+        Some(Qualification::Blocked) => "School".to_string(),
+        Some(Qualification::Outside(behind)) => format!("+{}", behind),
+        None => "".to_string()
+    }
+}
+
+fn number_field(label: &str, value: impl Binding<Filterless<String>> + Clone) -> impl IVP {
+    hstack()
+        .push(text(label.to_string()))
+        .push(
+            TextField::new(value)
+                .unstyled()
+                .padding(2)
+                .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                .intrinsic(40, 24)
+        )
+}
+
+fn toggle(label: &'static str, value: impl Binding<Filterless<bool>> + Clone) -> impl IVP {
+    ivp_using(move |_, s| {
+        let caption = value.map(move |on| {
+            format!("{}: {}", label, if *on { "on" } else { "off" })
+        }, s);
+
+        Button::new_with_label(
+            Text::from_signal(caption),
+            move |s| {
+                let curr = *value.borrow(s);
+                value.apply(Set(!curr), s);
+            }
+        )
+    })
+        .text_color(BLUE)
+}
+
+fn count(input: &str) -> Result<usize, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(0);
+    }
+
+    input.parse()
+        .map_err(|_| format!("Invalid count \"{}\"", input))
+}
+
+fn parse_rules(
+    gold: &str, silver: &str, bronze: &str, slots: &str,
+    per_institution: bool, honorable_mention: bool
+) -> Result<AwardRules, String> {
+    let medals = MedalLines {
+        gold: count(gold)?,
+        silver: count(silver)?,
+        bronze: count(bronze)?,
+    };
+    let advancement = Advancement {
+        slots: count(slots)?,
+        per_institution,
+    };
+
+    Ok(AwardRules {
+        medals: Some(medals).filter(|m| m.total() > 0),
+        advancement: Some(advancement).filter(|a| a.slots > 0),
+        honorable_mention,
+    })
+}
+
+// editor for the medal, honorable mention and advancement rules of the replay
+// affiliations tells whether the scoreboard names institutions
+pub(super) fn award_settings(rules: impl Binding<Filterless<Rules>> + Clone, affiliations: bool) -> impl IVP {
+    ivp_using(move |_, s| {
+        let current = rules.borrow(s).0;
+        let medals = current.medals.unwrap_or(MedalLines { gold: 0, silver: 0, bronze: 0 });
+        let advancement = current.advancement.unwrap_or(Advancement { slots: 0, per_institution: false });
+
+        let gold = Store::new(medals.gold.to_string());
+        let silver = Store::new(medals.silver.to_string());
+        let bronze = Store::new(medals.bronze.to_string());
+        let slots = Store::new(advancement.slots.to_string());
+        let per_institution = Store::new(advancement.per_institution);
+        let honorable_mention = Store::new(current.honorable_mention);
+
+        let (gold_b, silver_b, bronze_b, slots_b) = (gold.binding(), silver.binding(), bronze.binding(), slots.binding());
+        let (per_institution_b, honorable_mention_b) = (per_institution.binding(), honorable_mention.binding());
+        let rules = rules.clone();
+
+        hstack()
+            .push(text("Medals").bold())
+            .push(number_field("Gold", gold.binding()))
+            .push(number_field("Silver", silver.binding()))
+            .push(number_field("Bronze", bronze.binding()))
+            .push(toggle("Honorable mention", honorable_mention.binding()))
+            .push(text("Advancing").bold())
+            .push(number_field("Slots", slots.binding()))
+            .push(toggle("One per institution", per_institution.binding()))
+            .push(
+                text(if affiliations { "" } else { "(can't apply, this source lists no institutions)" })
+                    .text_color(DARK_GRAY)
+            )
+            .push(
+                button("Apply", move |s| {
+                    let parsed = parse_rules(
+                        &gold_b.borrow(s), &silver_b.borrow(s), &bronze_b.borrow(s), &slots_b.borrow(s),
+                        *per_institution_b.borrow(s), *honorable_mention_b.borrow(s)
+                    );
+
+                    match parsed {
+                        Ok(parsed) => rules.apply(Set(Rules(parsed)), s),
+                        Err(e) => {
+                            MessageBox::new("Invalid".into(), Some(&e))
+                                .button(MessageBoxButton::Ok)
+                                .run(|_, _| { });
+                        }
+                    }
+                })
+                    .text_color(BLUE)
+            )
+            .padding_edge(5, edge::DOWN | edge::LEFT)
+    })
+}
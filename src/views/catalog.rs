@@ -0,0 +1,84 @@
+use quarve::prelude::*;
+use quarve::state::{Filterless, Store};
+use quarve::state::SetAction::Set;
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::{TextField, TextModifier};
+use crate::IVP;
+use crate::catalog::{load, search, user_catalog_path, CatalogEntry};
+use crate::views::divider;
+
+fn entry_row(
+    entry: &CatalogEntry,
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+) -> impl IVP {
+    let chosen = entry.clone();
+
+    hstack()
+        .push(text(entry.year.to_string()).intrinsic(50, 22))
+        .push(text(entry.name.clone()).bold().intrinsic(300, 22))
+        .push(text(entry.region.clone()).intrinsic(160, 22))
+        .push(text(entry.source.clone()).text_color(DARK_GRAY).intrinsic(160, 22))
+        .push(
+            button("Use", move |s| {
+                contest_type.apply(Set(Some(chosen.source.clone())), s);
+                url.apply(Set(chosen.url.clone()), s);
+            })
+                .text_color(BLUE)
+        )
+}
+
+// searchable list of known contests that fills in the contest type and url
+pub fn catalog_browser(
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+) -> impl IVP {
+    let (entries, error) = match load() {
+        Ok(catalog) if catalog.skipped.is_empty() => (catalog.entries, "".to_string()),
+        Ok(catalog) => (catalog.entries, format!("Skipped {}", catalog.skipped.join("; "))),
+        Err(e) => (Vec::new(), e.to_string())
+    };
+    let query = Store::new("".to_string());
+    let query_binding = query.binding();
+
+    let results = ivp_using(move |_, s| {
+        let contest_type = contest_type.clone();
+        let url = url.clone();
+
+        query_binding.map(move |q| search(&entries, q), s)
+            .sig_vmap_options(move |entry, _s| {
+                entry_row(entry, contest_type.clone(), url.clone())
+            }, VStackOptions::default().spacing(0.0).align(HorizontalAlignment::Leading))
+    });
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(
+            hstack()
+                .push(text("Search").bold())
+                .push(
+                    TextField::new(query.binding())
+                        .unstyled()
+                        .padding(2)
+                        .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                        .intrinsic(300, 28)
+                )
+                .push(
+                    text(format!("Add your own contests to {}", user_catalog_path().display()))
+                        .text_size(10)
+                )
+        )
+        .push(
+            text(error)
+                .text_color(RED)
+        )
+        .push(
+            ScrollView::vertical(results)
+                .intrinsic(900, 160)
+        )
+        .push(divider())
+        .padding(5)
+}
@@ -0,0 +1,213 @@
+use std::ops::Deref;
+use quarve::prelude::*;
+use quarve::state::{Filterless, JoinedSignal, SetAction, Stateful};
+use quarve::state::SetAction::Set;
+use quarve::util::marker::FalseMarker;
+use quarve::view::color_view::EmptyView;
+use quarve::view::control::Dropdown;
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::TextModifier;
+use quarve::view::util::Color;
+use icpc_scoreboard::scoreboard::{Scoreboard, CONTEST_MINUTES};
+use icpc_scoreboard::stats::{bucket_count, rank_over_time, solves_over_time, CHART_BUCKET_MINUTES};
+use crate::IVP;
+use crate::views::{divider, problem_label, shown_problems, spoiler_gate};
+
+const BAR_WIDTH: f64 = 8.0;
+const SOLVES_HEIGHT: f64 = 40.0;
+const RANK_HEIGHT: f64 = 120.0;
+
+const TEAM_COLORS: [Color; 5] = [RED, BLUE, GREEN, ORANGE, PURPLE];
+
+// teams shown in the rank chart, as indices into the scoreboard
+#[derive(Clone, PartialEq)]
+struct ChartTeams(Vec<usize>);
+
+impl Stateful for ChartTeams {
+    type Action = SetAction<ChartTeams>;
+    type HasInnerStores = FalseMarker;
+}
+
+fn buckets() -> usize {
+    bucket_count(CONTEST_MINUTES * 60, CHART_BUCKET_MINUTES)
+}
+
+fn solves_row(index: usize, counts: &[usize], max: usize) -> impl IVP {
+    let bars = (0..buckets())
+        .map(|b| counts.get(b).map(|c| *c as f64 / max as f64 * SOLVES_HEIGHT))
+        .collect::<Vec<_>>()
+        .hmap_options(|height, _s| {
+            let height = height.unwrap_or(0.0);
+            BLUE
+                .intrinsic(BAR_WIDTH, height)
+                .padding_edge(1, edge::LEFT)
+        }, HStackOptions::default().spacing(0.0).align(VerticalAlignment::Bottom));
+
+    hstack()
+        .push(
+            text(problem_label(index))
+                .bold()
+                .intrinsic(30, 22)
+        )
+        .push(
+            bars
+                .frame(F.intrinsic(buckets() as f64 * (BAR_WIDTH + 1.0), SOLVES_HEIGHT).align(Alignment::BotLeading))
+                .border(LIGHT_GRAY, 1)
+        )
+        .push(
+            text(counts.last().copied().unwrap_or(0).to_string())
+                .intrinsic(40, 22)
+        )
+}
+
+pub(super) fn rank_row(team: String, ranks: &[usize], num_teams: usize, color: Color) -> impl IVP {
+    let points = (0..buckets())
+        .map(|b| ranks.get(b).map(|r| {
+            (*r - 1) as f64 / (num_teams.max(2) - 1) as f64 * (RANK_HEIGHT - 4.0)
+        }))
+        .collect::<Vec<_>>()
+        .hmap_options(move |offset, _s| {
+            let (offset, dot) = match offset {
+                Some(o) => (*o, color),
+                None => (0.0, CLEAR)
+            };
+
+            VStack::hetero_options(VStackOptions::default().spacing(0.0))
+                .push(EmptyView.intrinsic(BAR_WIDTH, offset))
+                .push(dot.intrinsic(BAR_WIDTH, 4))
+                .frame(F.intrinsic(BAR_WIDTH + 1.0, RANK_HEIGHT).align(Alignment::Top))
+        }, HStackOptions::default().spacing(0.0).align(VerticalAlignment::Top));
+
+    hstack()
+        .push(
+            text(team)
+                .bold()
+                .text_color(color)
+                .intrinsic(200, 22)
+        )
+        .push(
+            points
+                .border(LIGHT_GRAY, 1)
+        )
+        .push(
+            text(format!("#{}", ranks.last().copied().unwrap_or(0)))
+                .intrinsic(40, 22)
+        )
+}
+
+// cumulative solves per problem and rank of selected teams, up to the current replay minute
+pub fn charts(sb: &Scoreboard, timer: impl Binding<Filterless<usize>> + Clone, spoiler_safe: bool) -> impl IVP {
+    let selected = Store::new(ChartTeams(Vec::new()));
+    let choice = Store::new(None);
+
+    let solves_sb = sb.clone();
+    let solves_timer = timer.clone();
+    let solves = ivp_using(move |_, s| {
+        solves_timer.map(move |time| {
+            let counts = solves_over_time(&solves_sb, *time, CHART_BUCKET_MINUTES);
+            let max = counts.iter()
+                .filter_map(|c| c.last().copied())
+                .max()
+                .unwrap_or(0)
+                .max(1);
+
+            counts.into_iter()
+                .take(shown_problems(&solves_sb, *time, spoiler_safe))
+                .enumerate()
+                .map(|(i, c)| (i, c, max))
+                .collect::<Vec<_>>()
+        }, s)
+            .sig_vmap_options(|(i, c, max), _s| solves_row(*i, c, *max), VStackOptions::default().spacing(2.0).align(HorizontalAlignment::Leading))
+    });
+
+    let names = sb.entries.iter()
+        .map(|e| e.team.clone())
+        .collect::<Vec<_>>();
+    let add_names = names.clone();
+    let add_choice = choice.binding();
+    let add_selected = selected.binding();
+    let clear_selected = selected.binding();
+
+    // the dropdown lists every team of the final board, so it waits for the end while spoiler safe
+    let choice_binding = choice.binding();
+    let picker = spoiler_gate("team picker", timer.clone(), spoiler_safe, move || {
+        let add_names = add_names.clone();
+        let add_choice = add_choice.clone();
+        let add_selected = add_selected.clone();
+        let clear_selected = clear_selected.clone();
+
+        hstack()
+            .push(
+                text("Team")
+                    .bold()
+            )
+            .push(
+                Dropdown::new_with_options(choice_binding.clone(), names.clone())
+                    .intrinsic(200, 22)
+            )
+            .push(
+                button("Add", move |s| {
+                    let Some(name) = add_choice.borrow(s).deref().clone() else {
+                        return;
+                    };
+                    let Some(index) = add_names.iter().position(|n| *n == name) else {
+                        return;
+                    };
+
+                    let mut teams = add_selected.borrow(s).0.clone();
+                    if !teams.contains(&index) {
+                        teams.push(index);
+                    }
+                    add_selected.apply(Set(ChartTeams(teams)), s);
+                })
+            )
+            .push(
+                button("Clear", move |s| {
+                    clear_selected.apply(Set(ChartTeams(Vec::new())), s);
+                })
+            )
+            .padding(5)
+    });
+
+    let ranks_sb = sb.clone();
+    let ranks_selected = selected.binding();
+    let ranks = ivp_using(move |_, s| {
+        let ranks_sb2 = ranks_sb.clone();
+        JoinedSignal::join_map(&timer, &ranks_selected, move |time, teams| {
+            teams.0.iter()
+                .enumerate()
+                .map(|(slot, team)| (
+                    ranks_sb2.entries[*team].team.clone(),
+                    rank_over_time(&ranks_sb2, *team, *time, CHART_BUCKET_MINUTES),
+                    TEAM_COLORS[slot % TEAM_COLORS.len()],
+                ))
+                .collect::<Vec<_>>()
+        }, s)
+            .sig_vmap_options(move |(team, r, color), _s| {
+                rank_row(team.clone(), r, ranks_sb.entries.len(), *color)
+            }, VStackOptions::default().spacing(4.0).align(HorizontalAlignment::Leading))
+    });
+
+    ScrollView::vertical(
+        VStack::hetero_options(
+            VStackOptions::default()
+                .align(HorizontalAlignment::Leading)
+                .spacing(5.0)
+        )
+            .push(
+                text("Cumulative solves per problem")
+                    .bold()
+                    .padding(5)
+            )
+            .push(solves)
+            .push(divider())
+            .push(
+                text("Rank over time")
+                    .bold()
+                    .padding(5)
+            )
+            .push(picker)
+            .push(ranks)
+            .padding(5)
+    )
+}
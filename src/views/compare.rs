@@ -0,0 +1,223 @@
+use std::ops::Deref;
+use quarve::prelude::*;
+use quarve::state::{JoinedSignal, SetAction, Stateful, Store};
+use quarve::state::SetAction::Set;
+use quarve::util::marker::FalseMarker;
+use quarve::view::control::Dropdown;
+use quarve::view::modal::{MessageBox, MessageBoxButton};
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::{TextField, TextModifier};
+use icpc_scoreboard::compare::{compare, ProblemChange, Snapshot, TeamChange};
+use icpc_scoreboard::parser::CONTEST_TYPES;
+use icpc_scoreboard::scoreboard::{Scoreboard, CONTEST_MINUTES};
+use crate::IVP;
+use crate::loading::{begin_load, ScoreboardOption};
+use crate::views::{divider, problem_label};
+
+// replay minutes of the two snapshots being compared
+#[derive(Copy, Clone, PartialEq)]
+struct Minutes {
+    from: usize,
+    to: usize,
+}
+
+impl Stateful for Minutes {
+    type Action = SetAction<Minutes>;
+    type HasInnerStores = FalseMarker;
+}
+
+fn minute(input: &str) -> Result<usize, String> {
+    input.trim().parse::<usize>()
+        .ok()
+        .filter(|m| *m <= CONTEST_MINUTES)
+        .ok_or_else(|| format!("Invalid minute \"{}\", expected 0 to {}", input.trim(), CONTEST_MINUTES))
+}
+
+fn rank(rank: Option<usize>) -> String {
+    rank.map_or("-".to_string(), |r| r.to_string())
+}
+
+fn rate(rate: Option<f64>) -> String {
+    rate.map_or("-".to_string(), |r| format!("{:.0}%", r * 100.0))
+}
+
+fn team_row(change: &TeamChange) -> impl IVP {
+    let (delta, color) = match (change.before, change.after) {
+        (None, _) => ("new".to_string(), BLUE),
+        (_, None) => ("missing".to_string(), ORANGE),
+        _ => match change.rank_delta() {
+            Some(d) if d > 0 => (format!("+{}", d), GREEN),
+            Some(d) if d < 0 => (d.to_string(), RED),
+            _ => ("=".to_string(), BLACK),
+        }
+    };
+    let solved = change.newly_solved.iter()
+        .map(|p| problem_label(*p))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    hstack()
+        .push(text(change.team.clone()).bold().intrinsic(200, 22))
+        .push(text(rank(change.before)).intrinsic(60, 22))
+        .push(text(rank(change.after)).intrinsic(60, 22))
+        .push(text(delta).text_color(color).intrinsic(60, 22))
+        .push(text(solved).intrinsic(200, 22))
+}
+
+fn problem_row(change: &ProblemChange) -> impl IVP {
+    let delta = match (change.before, change.after) {
+        (Some(b), Some(a)) => format!("{:+.0}%", (a - b) * 100.0),
+        _ => "-".to_string()
+    };
+
+    hstack()
+        .push(text(problem_label(change.problem)).bold().intrinsic(60, 22))
+        .push(text(rate(change.before)).intrinsic(80, 22))
+        .push(text(rate(change.after)).intrinsic(80, 22))
+        .push(text(delta).intrinsic(80, 22))
+}
+
+// rank changes, new solves and problem difficulty between two minutes of the contest,
+// or between another contest and this one
+pub fn compare_view(sb: &Scoreboard) -> impl IVP {
+    let minutes = Store::new(Minutes { from: 120, to: CONTEST_MINUTES });
+    let from_input = Store::new("120".to_string());
+    let to_input = Store::new(CONTEST_MINUTES.to_string());
+    let other = Store::new(ScoreboardOption::None);
+    let other_type = Store::new(Some("AUTO".to_string()));
+    let other_url = Store::new("".to_string());
+
+    let (from_b, to_b, minutes_b) = (from_input.binding(), to_input.binding(), minutes.binding());
+    let (other_b, other_type_b, other_url_b) = (other.binding(), other_type.binding(), other_url.binding());
+    let clear_other = other.binding();
+
+    let sb = sb.clone();
+    let comparison_minutes = minutes.binding();
+    let comparison_other = other.binding();
+    let body = ivp_using(move |_, s| {
+        let sb = sb.clone();
+        let comparison = JoinedSignal::join_map(&comparison_minutes, &comparison_other, move |minutes, other| {
+            let after = Snapshot { sb: &sb, elapsed_seconds: minutes.to * 60 };
+            let before = match other {
+                ScoreboardOption::Some(other) => Snapshot { sb: other, elapsed_seconds: minutes.from * 60 },
+                ScoreboardOption::Loading(_) | ScoreboardOption::None => Snapshot { sb: &sb, elapsed_seconds: minutes.from * 60 },
+            };
+
+            compare(before, after)
+        }, s);
+
+        let teams = comparison.map(|c| c.teams.clone(), s)
+            .sig_vmap_options(|change, _s| team_row(change), VStackOptions::default().spacing(0.0));
+        let problems = comparison.map(|c| c.problems.clone(), s)
+            .sig_vmap_options(|change, _s| problem_row(change), VStackOptions::default().spacing(0.0));
+
+        VStack::hetero_options(
+            VStackOptions::default()
+                .align(HorizontalAlignment::Leading)
+                .spacing(0.0)
+        )
+            .push(
+                hstack()
+                    .push(text("Team").bold().intrinsic(200, 24))
+                    .push(text("Before").bold().intrinsic(60, 24))
+                    .push(text("After").bold().intrinsic(60, 24))
+                    .push(text("Change").bold().intrinsic(60, 24))
+                    .push(text("Newly solved").bold().intrinsic(200, 24))
+            )
+            .push(teams)
+            .push(divider())
+            .push(
+                hstack()
+                    .push(text("Problem").bold().intrinsic(60, 24))
+                    .push(text("Before").bold().intrinsic(80, 24))
+                    .push(text("After").bold().intrinsic(80, 24))
+                    .push(text("Change").bold().intrinsic(80, 24))
+            )
+            .push(problems)
+            .text_size(12)
+            .padding(5)
+    });
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(
+            hstack()
+                .push(text("From minute").bold())
+                .push(
+                    TextField::new(from_input.binding())
+                        .unstyled()
+                        .padding(2)
+                        .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                        .intrinsic(50, 24)
+                )
+                .push(text("To minute").bold())
+                .push(
+                    TextField::new(to_input.binding())
+                        .unstyled()
+                        .padding(2)
+                        .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                        .intrinsic(50, 24)
+                )
+                .push(
+                    button("Apply", move |s| {
+                        match (minute(&from_b.borrow(s)), minute(&to_b.borrow(s))) {
+                            (Ok(from), Ok(to)) => minutes_b.apply(Set(Minutes { from, to }), s),
+                            (Err(e), _) | (_, Err(e)) => {
+                                MessageBox::new("Invalid".into(), Some(&e))
+                                    .button(MessageBoxButton::Ok)
+                                    .run(|_, _| { });
+                            }
+                        }
+                    })
+                        .text_color(BLUE)
+                )
+                .padding_edge(5, edge::UP | edge::LEFT)
+        )
+        .push(
+            hstack()
+                .push(text("Earlier contest").bold())
+                .push(
+                    Dropdown::new_with_options(
+                        other_type.binding(),
+                        CONTEST_TYPES.iter().map(|t| t.to_string()).collect()
+                    )
+                        .intrinsic(170, 22)
+                )
+                .push(
+                    TextField::new(other_url.binding())
+                        .unstyled()
+                        .padding(2)
+                        .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                        .intrinsic(300, 24)
+                )
+                .push(
+                    button("Load", move |s| {
+                        let Some(contest_type) = other_type_b.borrow(s).deref().clone() else {
+                            MessageBox::new("Invalid".into(), "Select a contest type".into())
+                                .button(MessageBoxButton::Ok)
+                                .run(|_, _| { });
+                            return;
+                        };
+
+                        let url = other_url_b.borrow(s).clone();
+                        let other = other_b.clone();
+                        tokio::spawn(async move {
+                            begin_load(&contest_type, &url, other).await
+                        });
+                    })
+                        .text_color(BLUE)
+                )
+                .push(
+                    button("Clear", move |s| {
+                        clear_other.apply(Set(ScoreboardOption::None), s);
+                    })
+                        .text_color(BLUE)
+                )
+                .padding_edge(5, edge::UP | edge::LEFT)
+        )
+        .push(divider())
+        .push(ScrollView::vertical(body))
+}
@@ -0,0 +1,738 @@
+use std::ops::Deref;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use quarve::core::slock_owner;
+use quarve::prelude::*;
+use quarve::state::{Binding, Filterless, JoinedSignal, Store, WeakBinding};
+use quarve::state::{SetAction, Stateful};
+use quarve::state::SetAction::Set;
+use quarve::util::marker::FalseMarker;
+use quarve::view::color_view::EmptyView;
+use quarve::view::control::{Button, Dropdown};
+use quarve::view::modal::{MessageBox, MessageBoxButton};
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::{Text, TextField, TextModifier};
+use quarve::view_match;
+use icpc_scoreboard::parser::CONTEST_TYPES;
+use icpc_scoreboard::replay::{format_clock, parse_start, replay_clock};
+use icpc_scoreboard::scoreboard::{problem_label, AwardRules, ProblemStatus, Qualification, Scoreboard, TeamResult, CONTEST_MINUTES};
+use crate::IVP;
+use crate::loading::{begin_parse, ScoreboardOption};
+use crate::session::{Session, WindowSession};
+use crate::views::awards::{award_color, award_settings, qualification_label, standings, Rules, Standing};
+use crate::views::catalog::catalog_browser;
+use crate::views::charts::charts;
+use crate::views::compare::compare_view;
+use crate::views::start::start_screen;
+use crate::views::resolver::resolver;
+use crate::views::stats::stats_table;
+use crate::views::team::team_detail;
+
+mod awards;
+mod catalog;
+mod charts;
+mod compare;
+mod resolver;
+mod start;
+mod stats;
+mod team;
+
+// which presentation of a loaded contest is shown
+#[derive(Copy, Clone, PartialEq)]
+enum ViewMode {
+    Replay,
+    Resolver,
+    Charts,
+    Compare
+}
+
+impl Stateful for ViewMode {
+    type Action = SetAction<ViewMode>;
+    type HasInnerStores = FalseMarker;
+}
+
+#[allow(unused)]
+fn dummy_scoreboard() -> Scoreboard {
+    Scoreboard {
+        name: String::new(),
+        num_problems: 10,
+        problem_names: Vec::new(),
+        entries: vec![
+            TeamResult {
+                team: "UCSD 1".to_string(),
+                problems: vec![
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Incorrect,
+                    ProblemStatus::Solved {
+                        attempts: 2,
+                        seconds: 301 * 60,
+                        first_solve: false,
+                    }
+                ],
+                regional_champion: None,
+                affiliation: None,
+            },
+            TeamResult {
+                team: "UCSD 2".to_string(),
+                problems: vec![
+                    ProblemStatus::Solved {
+                        attempts: 3,
+                        seconds: 140 * 60,
+                        first_solve: false,
+                    },
+                    ProblemStatus::Solved {
+                        attempts: 1,
+                        seconds: 300 * 60,
+                        first_solve: true,
+                    }
+                ],
+                regional_champion: None,
+                affiliation: None,
+            }
+        ],
+        awards: AwardRules::default(),
+    }
+}
+
+fn divider() -> impl IVP {
+    LIGHT_GRAY
+        .frame(F.intrinsic(1,1).unlimited_width())
+}
+
+// restored is a running replay to resume,
+// session follows whichever contest the window goes on to replay
+pub fn viewer(restored: Option<Session>, session: impl Binding<Filterless<WindowSession>> + Clone) -> impl IVP {
+    let contest_type = Store::new(Some(restored.as_ref().map_or("AUTO".to_string(), |session| session.contest_type.clone())));
+    let url = Store::new(restored.as_ref().map_or("".to_string(), |session| session.url.clone()));
+    let start_input = Store::new("".to_string());
+    let contest_data = Store::new(ScoreboardOption::None);
+    let spoiler_safe = Store::new(false);
+    let show_catalog = Store::new(false);
+    let (catalog_type, catalog_url) = (contest_type.binding(), url.binding());
+
+    if let Some(session) = restored {
+        let contest_data = contest_data.binding();
+        tokio::spawn(async move {
+            begin_parse(&session.contest_type, &session.url, contest_data).await
+        });
+    }
+
+    vstack()
+        .push(
+            text("ICPC Live Scoreboard")
+                .text_size(36)
+                .padding(10)
+        )
+        .push(selector(
+            contest_type.binding(), url.binding(), start_input.binding(),
+            session.clone(), contest_data.binding(), spoiler_safe.binding(),
+            show_catalog.binding()
+        ))
+        .push(
+            view_match!(show_catalog.binding();
+                true => catalog_browser(catalog_type.clone(), catalog_url.clone()),
+                false => EmptyView
+            )
+        )
+        .push(divider())
+        .push(main_content(
+            contest_type.binding(), url.binding(),
+            contest_data.binding(), spoiler_safe.binding(), session
+        ))
+        .frame(F.unlimited_stretch())
+        .text_color(WHITE)
+        .bg_color(BLACK)
+}
+
+// starts replaying a contest and remembers it as one of the running sessions,
+// in place of the one the window was replaying before
+fn open_contest(
+    contest_type: String,
+    url: String,
+    start: u64,
+    window_session: &impl Binding<Filterless<WindowSession>>,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+    s: MSlock
+) {
+    let previous = window_session.borrow(s).0.clone();
+    if let Some(previous) = previous.filter(|previous| previous.url != url) {
+        if let Err(e) = Session::remove(&previous.url) {
+            MessageBox::new("Unable to remove session".into(), Some(&e.to_string()))
+                .button(MessageBoxButton::Ok)
+                .run(|_, _| { });
+        }
+    }
+
+    let session = Session {
+        contest_type: contest_type.clone(),
+        url: url.clone(),
+        start,
+    };
+    window_session.apply(Set(WindowSession(Some(session.clone()))), s);
+    if let Err(e) = session.save() {
+        MessageBox::new("Unable to save session".into(), Some(&e.to_string()))
+            .button(MessageBoxButton::Ok)
+            .run(|_, _| { });
+    }
+
+    tokio::spawn(async move {
+        begin_parse(&contest_type, &url, contest_data).await
+    });
+}
+
+fn selector(
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+    start_input: impl Binding<Filterless<String>> + Clone,
+    session: impl Binding<Filterless<WindowSession>> + Clone,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+    spoiler_safe: impl Binding<Filterless<bool>> + Clone,
+    show_catalog: impl Binding<Filterless<bool>> + Clone,
+) -> impl IVP {
+    let toggle_safe = spoiler_safe.clone();
+
+    hstack()
+        .push(
+            text("Contest Type")
+                .bold()
+        )
+        .push(
+            Dropdown::new_with_options(
+                contest_type.clone(),
+                CONTEST_TYPES.iter().map(|t| t.to_string()).collect()
+            )
+                .intrinsic(170, 22)
+        )
+        .push(
+            button("Catalog", move |s| {
+                let curr = *show_catalog.borrow(s);
+                show_catalog.apply(Set(!curr), s);
+            })
+                .text_color(BLUE)
+        )
+        .push(
+            text("Scoreboard URL:")
+                .bold()
+        )
+        .push(
+            TextField::new(url.clone())
+                .unstyled()
+                .padding(2)
+                .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                .intrinsic(300, 28)
+        )
+        .push(
+            text("Start:")
+                .bold()
+        )
+        .push(
+            TextField::new(start_input.clone())
+                .unstyled()
+                .padding(2)
+                .layer(L.border(LIGHT_GRAY, 1).radius(2))
+                .intrinsic(60, 28)
+        )
+        .push(
+            button("Go", move |s| {
+                match contest_type.borrow(s).deref() {
+                    Some(ref content) => {
+                        let start = match parse_start(&start_input.borrow(s), SystemTime::now()) {
+                            Ok(start) => start,
+                            Err(e) => {
+                                MessageBox::new("Invalid".into(), Some(&e))
+                                    .button(MessageBoxButton::Ok)
+                                    .run(|_, _| { });
+                                return;
+                            }
+                        };
+
+                        open_contest(content.clone(), url.borrow(s).clone(), start, &session, contest_data.clone(), s);
+                    }
+                    None => {
+                        MessageBox::new("Invalid".into(), "Select a contest type".into())
+                            .button(MessageBoxButton::Ok)
+                            .run(|_, _| { });
+                    }
+                }
+            })
+                .text_color(BLUE)
+        )
+        .push(
+            ivp_using(move |_, s| {
+                let label = toggle_safe.map(|safe| {
+                    if *safe { "Spoiler-safe: on" } else { "Spoiler-safe: off" }.to_string()
+                }, s);
+
+                Button::new_with_label(
+                    Text::from_signal(label),
+                    move |s| {
+                        let curr = *toggle_safe.borrow(s);
+                        toggle_safe.apply(Set(!curr), s);
+                    }
+                )
+            })
+                .text_color(BLUE)
+        )
+        .padding(5)
+}
+
+// seconds elapsed since the replay started,
+// and seconds remaining until it starts
+fn replay_timer(start: SystemTime) -> (Store<usize>, Store<usize>) {
+    let timer = Store::new(0);
+    let countdown = Store::new(0);
+
+    let timer_binding = timer.weak_binding();
+    let countdown_binding = countdown.weak_binding();
+    thread::spawn(move || {
+        loop {
+            let (Some(timer), Some(countdown)) = (timer_binding.upgrade(), countdown_binding.upgrade()) else {
+                break;
+            };
+
+            let (elapsed, remaining) = replay_clock(start, SystemTime::now());
+
+            {
+                let s = slock_owner();
+                if *timer.borrow(s.marker()) != elapsed {
+                    timer.apply(Set(elapsed), s.marker());
+                }
+                if *countdown.borrow(s.marker()) != remaining {
+                    countdown.apply(Set(remaining), s.marker());
+                }
+            }
+
+            // do not keep the stores alive while sleeping
+            drop((timer, countdown));
+            thread::sleep(Duration::from_secs(1));
+        }
+    });
+
+    (timer, countdown)
+}
+
+fn mode_button(label: &str, target: ViewMode, mode: impl Binding<Filterless<ViewMode>>) -> impl IVP {
+    button(label.to_string(), move |s| {
+        mode.apply(Set(target), s);
+    })
+}
+
+// in spoiler-safe mode the resolver and comparisons show final results,
+// so they only become available once the replay is over
+fn spoiler_gate<V: IVP>(
+    feature: &'static str,
+    timer: impl Binding<Filterless<usize>> + Clone,
+    spoiler_safe: bool,
+    view: impl Fn() -> V + Send + 'static
+) -> impl IVP {
+    ivp_using(move |_, s| {
+        let unlocked = timer.map(move |time| !spoiler_safe || *time >= CONTEST_MINUTES * 60, s);
+
+        view_match!(unlocked;
+            true => {
+                view()
+            },
+            false => {
+                text(format!("The {} unlocks once the replay reaches the end of the contest", feature))
+                    .padding(10)
+            }
+        )
+    })
+}
+
+// problems to show at time, leaving out untouched ones at the end while spoiler safe
+fn shown_problems(sb: &Scoreboard, time: usize, spoiler_safe: bool) -> usize {
+    if spoiler_safe {
+        sb.problems_seen(time)
+    } else {
+        sb.num_problems
+    }
+}
+
+fn contest(sb: &Scoreboard, spoiler_safe: bool, start: u64) -> impl IVP {
+    // the timer lives here so that switching modes does not restart the replay
+    let (timer, countdown) = replay_timer(UNIX_EPOCH + Duration::from_secs(start));
+    let countdown = countdown.binding();
+    let mode = Store::new(ViewMode::Replay);
+    let sb = sb.clone();
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(
+            hstack()
+                .push(mode_button("Replay", ViewMode::Replay, mode.binding()))
+                .push(mode_button("Resolver", ViewMode::Resolver, mode.binding()))
+                .push(mode_button("Charts", ViewMode::Charts, mode.binding()))
+                .push(mode_button("Compare", ViewMode::Compare, mode.binding()))
+                .push(
+                    ivp_using(move |_, s| {
+                        Text::from_signal(countdown.map(|remaining| {
+                            if *remaining == 0 {
+                                "".to_string()
+                            } else {
+                                format!("Starts in {:0>2}:{:0>2}", remaining / 60, remaining % 60)
+                            }
+                        }, s))
+                            .bold()
+                    })
+                )
+                .padding_edge(5, edge::UP | edge::LEFT)
+        )
+        .push(
+            view_match!(mode.binding();
+                ViewMode::Replay => {
+                    scoreboard(&sb, timer.binding(), spoiler_safe)
+                },
+                ViewMode::Resolver => {
+                    let sb = sb.clone();
+                    spoiler_gate("resolver", timer.binding(), spoiler_safe, move || resolver(&sb))
+                },
+                ViewMode::Charts => {
+                    charts(&sb, timer.binding(), spoiler_safe)
+                },
+                ViewMode::Compare => {
+                    let sb = sb.clone();
+                    spoiler_gate("comparison", timer.binding(), spoiler_safe, move || compare_view(&sb))
+                }
+            )
+        )
+}
+
+fn scoreboard(sb: &Scoreboard, timer: impl Binding<Filterless<usize>> + Clone, spoiler_safe: bool) -> impl IVP {
+    // timer controls
+    let timer_sig = timer.clone();
+    let show_stats = Store::new(false);
+    let toggle_stats = show_stats.binding();
+    let show_awards = Store::new(false);
+    let toggle_awards = show_awards.binding();
+    let controls =
+        ivp_using(move |_, s| {
+            hstack()
+                .push(
+                    Text::from_signal(timer_sig.map(|time| format!("Time {}", format_clock(*time)), s))
+                        .padding(5)
+                        .frame(F.intrinsic(110, 30).align(Alignment::Leading) )
+                        .border(LIGHT_GRAY, 1)
+                        .padding_edge(5, edge::DOWN | edge::LEFT)
+                )
+                .push(
+                    button("Statistics", move |s| {
+                        let curr = *toggle_stats.borrow(s);
+                        toggle_stats.apply(Set(!curr), s);
+                    })
+                        .padding_edge(5, edge::DOWN)
+                )
+                .push(
+                    button("Awards", move |s| {
+                        let curr = *toggle_awards.borrow(s);
+                        toggle_awards.apply(Set(!curr), s);
+                    })
+                        .padding_edge(5, edge::DOWN)
+                )
+        });
+
+    let sb = sb.clone();
+    let stats_sb = sb.clone();
+    let affiliations = sb.has_affiliations();
+    let stats_timer = timer.clone();
+
+    // problem headers
+    let header_sb = sb.clone();
+    let header_timer = timer.clone();
+    let problems = ivp_using(move |_, s| {
+        header_timer.map(move |time| {
+            (0..shown_problems(&header_sb, *time, spoiler_safe)).collect::<Vec<_>>()
+        }, s)
+            .sig_hmap_options(|i, _s| {
+                text(problem_label(*i))
+                    .intrinsic(22, 22)
+                    .bold()
+                    .padding(3)
+                    .layer(L.radius(2).border(DARK_GRAY, 1))
+                    .intrinsic(50, 30)
+            }, HStackOptions::default().spacing(0.0))
+    });
+
+    // team whose detail panel is open
+    let selected_team = Store::new(None);
+    let select = selected_team.binding();
+    let detail_sb = sb.clone();
+    let detail_timer = timer.clone();
+
+    // award rules start out as published by the source and can be edited during the replay
+    let rules = Store::new(Rules(sb.awards));
+    let rules_binding = rules.binding();
+    let items = ivp_using(move |_, s| {
+        let sorted_items = JoinedSignal::join_map(&timer, &rules_binding, move |time, rules| {
+            let sb = Scoreboard {
+                awards: rules.0,
+                ..sb.clone()
+            };
+
+            let shown = shown_problems(&sb, *time, spoiler_safe);
+            let mut rows = standings(&sb, *time);
+            for row in rows.iter_mut() {
+                row.result.problems.truncate(shown);
+            }
+
+            rows
+        }, s);
+
+        sorted_items
+            .sig_vmap_options(move |Standing { rank: index, team: team_index, result: se, award, qualification, line }, s| {
+                let se2 = se.clone();
+                let solved_time = timer.map(move |time| {
+                    let res = se2.score(*time);
+                    res
+                }, s);
+                let solved = solved_time.map(|(s, _)| (-s).to_string(), s);
+                let time = solved_time.map(|(_, t)| t.to_string(), s);
+                let score =
+                    HStack::hetero_options(
+                        HStackOptions::default()
+                            .spacing(1.0)
+                    )
+                        .push(
+                            Text::from_signal(solved)
+                                .bold()
+                        )
+                        .push(
+                            Text::from_signal(time)
+                                .text_color(DARK_GRAY)
+                                .text_size(10)
+                        )
+                        .intrinsic(58, 38)
+                        .border(LIGHT_GRAY, 1)
+                        .intrinsic(60, 40);
+
+                let timer_sig = timer.clone();
+                let solves = se.problems.clone()
+                    .hmap_options(move |solve, s| {
+                        let signal = JoinedSignal::join_map(
+                            &timer_sig, &FixedSignal::new(solve.clone()),
+                            |u, v| {
+                                v.visible_at(*u)
+                            }, s
+                        );
+
+                        view_match!(signal;
+                            ProblemStatus::Incorrect => {
+                                CLEAR
+                                    .intrinsic(50, 40)
+                            },
+                            ProblemStatus::Pending { attempts } => {
+                                VStack::hetero_options(
+                                    VStackOptions::default()
+                                    .spacing(4.0)
+                                )
+                                    .push(
+                                        text("...")
+                                    )
+                                    .push(
+                                        text(attempts.to_string() + if *attempts == 1 { " try" } else { " tries"})
+                                        .text_size(10)
+                                    )
+                                    .intrinsic(50, 40)
+                                    .bg_color(rgb(144, 202, 249))
+                            },
+                            ProblemStatus::Frozen { attempts, pending } => {
+                                VStack::hetero_options(
+                                    VStackOptions::default()
+                                    .spacing(4.0)
+                                )
+                                    .push(
+                                        text("?")
+                                            .bold()
+                                    )
+                                    .push(
+                                        text(format!("{} + {}", attempts, pending))
+                                        .text_size(10)
+                                    )
+                                    .intrinsic(50, 40)
+                                    .bg_color(rgb(255, 204, 0))
+                            },
+                            ProblemStatus::Solved { attempts, seconds, first_solve } => {
+                                let color = if *first_solve {
+                                    rgb(32, 159, 23)
+                                } else {
+                                    rgb(84, 231, 77)
+                                };
+
+                                VStack::hetero_options(
+                                    VStackOptions::default()
+                                    .spacing(4.0)
+                                )
+                                    .push(
+                                        text((seconds / 60).to_string())
+                                    )
+                                    .push(
+                                        text(attempts.to_string() + if *attempts == 1 { " try" } else { " tries"})
+                                        .text_size(10)
+                                    )
+                                    .intrinsic(50, 40)
+                                    .bg_color(color)
+                            }
+                        )
+                    }, HStackOptions::default().spacing(0.0))
+                    .text_color(BLACK);
+
+                let name = VStack::hetero_options(VStackOptions::default()
+                    .align(HorizontalAlignment::Leading)
+                    .spacing(0.0)
+                )
+                    .push(
+                        text(se.team.clone())
+                            .bold()
+                    )
+                    .push(
+                        text(se.regional_champion.clone().or(se.affiliation.clone()).unwrap_or_default())
+                            .text_size(10)
+                            .text_color(DARK_GRAY)
+                    )
+                    .frame(F.intrinsic(200, 30).align(Alignment::Leading));
+
+                let select = select.clone();
+                let team_index = *team_index;
+                Button::new_with_label(
+                    VStack::hetero_options(VStackOptions::default()
+                        .align(HorizontalAlignment::Leading)
+                        .spacing(0.0)
+                    )
+                        .push(
+                            hstack()
+                                .push(
+                                    if matches!(qualification, Some(Qualification::Advancing(_))) { GREEN } else { CLEAR }
+                                        .intrinsic(4, 30)
+                                )
+                                .push(
+                                    text((index + 1).to_string())
+                                        .intrinsic(46, 30)
+                                        .bg_color(award_color(*award))
+                                )
+                                .push(name)
+                                .push(score)
+                                .push(
+                                    text(qualification_label(*qualification))
+                                        .text_size(10)
+                                        .intrinsic(50, 30)
+                                )
+                                .push(solves)
+                        )
+                        .push(
+                            line.unwrap_or(LIGHT_GRAY)
+                                .frame(F.intrinsic(1, if line.is_some() { 3 } else { 1 }).unlimited_width())
+                        ),
+                    move |s| {
+                        select.apply(Set(Some(team_index)), s);
+                    }
+                )
+            }, VStackOptions::default().spacing(0.0))
+    });
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(controls)
+        .push(
+            hstack()
+                .push(
+                    EmptyView.intrinsic(50, 30)
+                )
+                .push(
+                    text("Team")
+                        .bold()
+                        .text_color(RED)
+                        .intrinsic(200, 30)
+                )
+                .push(
+                    text("Score")
+                        .bold()
+                        .text_color(BLUE)
+                        .intrinsic(60, 30)
+                )
+                .push(
+                    text("Adv")
+                        .bold()
+                        .intrinsic(50, 30)
+                )
+                .push(problems)
+                .padding_edge(10, edge::DOWN)
+        )
+        .push(divider())
+        .push(
+            view_match!(show_awards.binding();
+                true => award_settings(rules.binding(), affiliations),
+                false => EmptyView
+            )
+        )
+        .push(
+            view_match!(show_stats.binding();
+                true => stats_table(&stats_sb, stats_timer.clone(), spoiler_safe),
+                false => EmptyView
+            )
+        )
+        .push(
+            HStack::hetero_options(
+                HStackOptions::default()
+                    .align(VerticalAlignment::Top)
+                    .spacing(0.0)
+            )
+                .push(
+                    ScrollView::vertical(
+                        vstack()
+                            .push(items)
+                    )
+                )
+                .push(
+                    view_match!(selected_team.binding();
+                        Some(team) => {
+                            team_detail(&detail_sb, *team, detail_timer.clone(), selected_team.binding())
+                        },
+                        None => {
+                            EmptyView
+                        }
+                    )
+                )
+        )
+}
+
+fn main_content(
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+    spoiler_safe: impl Binding<Filterless<bool>> + Clone,
+    session: impl Binding<Filterless<WindowSession>> + Clone,
+) -> impl IVP {
+
+    ivp_using(move |_, s| {
+        let shown = JoinedSignal::join_map(&contest_data, &spoiler_safe, |data, safe| {
+            match data {
+                ScoreboardOption::Some(sb) if *safe => (ScoreboardOption::Some(sb.without_spoilers()), true),
+                other => (other.clone(), *safe)
+            }
+        }, s);
+        let shown = JoinedSignal::join_map(&shown, &session, |(data, safe), session| {
+            (data.clone(), *safe, session.0.as_ref().map_or(0, |session| session.start))
+        }, s);
+
+        view_match!(shown;
+            (ScoreboardOption::Some(sb), safe, start) => {
+                contest(sb, *safe, *start)
+            },
+            (ScoreboardOption::Loading(status), _, _) => {
+                text(status.clone())
+                    .frame(F.unlimited_stretch())
+            },
+            (ScoreboardOption::None, _, _) => {
+                start_screen(contest_type.clone(), url.clone(), session.clone(), contest_data.clone())
+            }
+        )
+    })
+        .bg_color(WHITE)
+        .text_color(BLACK)
+}
\ No newline at end of file
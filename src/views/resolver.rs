@@ -0,0 +1,192 @@
+use quarve::prelude::*;
+use quarve::state::{SetAction, Stateful};
+use quarve::state::SetAction::Set;
+use quarve::util::marker::FalseMarker;
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::{Text, TextModifier};
+use quarve::view_match;
+use icpc_scoreboard::resolver::{Resolver, ResolverRow};
+use icpc_scoreboard::scoreboard::{ProblemStatus, Scoreboard, CONTEST_MINUTES, FREEZE_MINUTES};
+use crate::IVP;
+use crate::views::divider;
+
+// the resolver as app state, kept out of the library
+#[derive(Clone, PartialEq)]
+struct ResolverState(Resolver);
+
+impl Stateful for ResolverState {
+    type Action = SetAction<ResolverState>;
+    type HasInnerStores = FalseMarker;
+}
+
+// cell contents as shown by the resolver
+#[derive(Clone, PartialEq)]
+enum Cell {
+    Empty,
+    Pending,
+    Solved {
+        attempts: usize,
+        seconds: usize,
+        first_solve: bool,
+    },
+}
+
+fn cell(cell: Cell) -> impl IVP {
+    view_match!(FixedSignal::new(cell);
+        Cell::Empty => {
+            CLEAR
+                .intrinsic(50, 40)
+        },
+        Cell::Pending => {
+            text("?")
+                .bold()
+                .intrinsic(50, 40)
+                .bg_color(rgb(255, 204, 0))
+        },
+        Cell::Solved { attempts, seconds, first_solve } => {
+            let color = if *first_solve {
+                rgb(32, 159, 23)
+            } else {
+                rgb(84, 231, 77)
+            };
+
+            VStack::hetero_options(
+                VStackOptions::default()
+                    .spacing(4.0)
+            )
+                .push(
+                    text((seconds / 60).to_string())
+                )
+                .push(
+                    text(attempts.to_string() + if *attempts == 1 { " try" } else { " tries"})
+                        .text_size(10)
+                )
+                .intrinsic(50, 40)
+                .bg_color(color)
+        }
+    )
+}
+
+fn row(rank: usize, row: &ResolverRow, highlighted: bool) -> impl IVP {
+    let (solved, time) = row.visible.score(CONTEST_MINUTES * 60);
+
+    let cells = row.visible.problems.iter()
+        .zip(row.pending.iter())
+        .map(|(p, pending)| match p {
+            _ if *pending => Cell::Pending,
+            ProblemStatus::Incorrect | ProblemStatus::Pending { .. } | ProblemStatus::Frozen { .. } => Cell::Empty,
+            ProblemStatus::Solved { attempts, seconds, first_solve } => Cell::Solved {
+                attempts: *attempts,
+                seconds: *seconds,
+                first_solve: *first_solve,
+            }
+        })
+        .collect::<Vec<_>>()
+        .hmap_options(|c, _s| cell(c.clone()), HStackOptions::default().spacing(0.0));
+
+    let bg = if highlighted {
+        rgb(187, 222, 251)
+    } else {
+        CLEAR
+    };
+
+    VStack::hetero_options(VStackOptions::default()
+        .align(HorizontalAlignment::Leading)
+        .spacing(0.0)
+    )
+        .push(
+            hstack()
+                .push(
+                    text((rank + 1).to_string())
+                        .intrinsic(50, 30)
+                )
+                .push(
+                    text(row.visible.team.clone())
+                        .intrinsic(200, 30)
+                        .bold()
+                )
+                .push(
+                    HStack::hetero_options(
+                        HStackOptions::default()
+                            .spacing(1.0)
+                    )
+                        .push(
+                            text((-solved).to_string())
+                                .bold()
+                        )
+                        .push(
+                            text(time.to_string())
+                                .text_color(DARK_GRAY)
+                                .text_size(10)
+                        )
+                        .intrinsic(58, 38)
+                        .border(LIGHT_GRAY, 1)
+                        .intrinsic(60, 40)
+                )
+                .push(cells)
+                .bg_color(bg)
+        )
+        .push(divider())
+}
+
+// ICPC style resolver that starts at the frozen standings
+// and reveals pending submissions from the bottom up
+pub fn resolver(sb: &Scoreboard) -> impl IVP {
+    let state = Store::new(ResolverState(Resolver::new(sb, FREEZE_MINUTES)));
+    let binding = state.binding();
+    let keys = state.binding();
+    let sb = sb.clone();
+
+    let status_sig = state.binding();
+    let status = ivp_using(move |_, s| {
+        Text::from_signal(status_sig.map(|ResolverState(r)| {
+            if r.is_done() {
+                "All submissions revealed (r to restart)".to_string()
+            } else {
+                "Space to reveal the next submission, f to reveal all, r to restart".to_string()
+            }
+        }, s))
+            .padding(5)
+    });
+
+    let rows = ivp_using(move |_, s| {
+        binding.map(|ResolverState(r)| {
+            let highlighted = r.last_reveal().map(|rev| rev.team);
+            r.standings()
+                .into_iter()
+                .enumerate()
+                .map(|(rank, row)| {
+                    let h = highlighted == Some(row.index);
+                    (rank, row, h)
+                })
+                .collect::<Vec<_>>()
+        }, s)
+            .sig_vmap_options(|(rank, r, h), _s| row(*rank, r, *h), VStackOptions::default().spacing(0.0))
+    });
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(status)
+        .push(divider())
+        .push(
+            ScrollView::vertical(
+                vstack()
+                    .push(rows)
+            )
+        )
+        .key_listener(move |key, _modifiers, s| {
+            let mut next = keys.borrow(s).0.clone();
+            match key {
+                " " | "n" => {
+                    next.step();
+                }
+                "f" => next.finish(),
+                "r" => next = Resolver::new(&sb, FREEZE_MINUTES),
+                _ => return
+            }
+            keys.apply(Set(ResolverState(next)), s);
+        })
+}
@@ -0,0 +1,157 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use chrono::{Local, TimeZone};
+use quarve::prelude::*;
+use quarve::state::{Filterless, Store};
+use quarve::state::SetAction::Set;
+use quarve::view::modal::{MessageBox, MessageBoxButton};
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::TextModifier;
+use icpc_scoreboard::parser::CONTEST_TYPES;
+use crate::IVP;
+use crate::history::{ContestEntry, History};
+use crate::loading::ScoreboardOption;
+use crate::session::WindowSession;
+use crate::views::{divider, open_contest};
+
+fn date(unix_seconds: u64) -> String {
+    Local.timestamp_opt(unix_seconds as i64, 0)
+        .single()
+        .map_or("".to_string(), |d| d.format("%Y-%m-%d %H:%M").to_string())
+}
+
+fn entry_row(
+    entry: &ContestEntry,
+    bookmarked: bool,
+    history: impl Binding<Filterless<History>> + Clone,
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+    session: impl Binding<Filterless<WindowSession>> + Clone,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+) -> impl IVP {
+    let open_entry = entry.clone();
+    let bookmark_entry = entry.clone();
+
+    hstack()
+        .push(text(entry.name.clone()).bold().intrinsic(200, 22))
+        .push(text(entry.contest_type.clone()).intrinsic(150, 22))
+        .push(text(entry.url.clone()).text_color(DARK_GRAY).intrinsic(320, 22))
+        .push(text(date(entry.date)).intrinsic(130, 22))
+        .push(
+            button("Open", move |s| {
+                // entries from older versions may name sources that no longer exist
+                if !CONTEST_TYPES.contains(&open_entry.contest_type.as_str()) {
+                    MessageBox::new(
+                        "Unable to open".into(),
+                        Some(&format!("Unknown contest type \"{}\"", open_entry.contest_type))
+                    )
+                        .button(MessageBoxButton::Ok)
+                        .run(|_, _| { });
+                    return;
+                }
+
+                contest_type.apply(Set(Some(open_entry.contest_type.clone())), s);
+                url.apply(Set(open_entry.url.clone()), s);
+
+                let start = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_secs());
+                open_contest(
+                    open_entry.contest_type.clone(), open_entry.url.clone(), start,
+                    &session, contest_data.clone(), s
+                );
+            })
+                .text_color(BLUE)
+        )
+        .push(
+            button(if bookmarked { "Remove bookmark" } else { "Bookmark" }, move |s| {
+                let mut updated = history.borrow(s).clone();
+                updated.toggle_bookmark(&bookmark_entry);
+                // the list still updates for this run if saving fails
+                let _ = updated.save();
+                history.apply(Set(updated), s);
+            })
+                .text_color(BLUE)
+        )
+}
+
+fn section(
+    title: &str,
+    entries: impl Fn(&History) -> Vec<ContestEntry> + Send + 'static,
+    history: impl Binding<Filterless<History>> + Clone,
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+    session: impl Binding<Filterless<WindowSession>> + Clone,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+) -> impl IVP {
+    let title = title.to_string();
+
+    ivp_using(move |_, s| {
+        let rows = history.map(move |h| {
+            entries(h).into_iter()
+                .map(|e| {
+                    let bookmarked = h.is_bookmarked(&e);
+                    (e, bookmarked)
+                })
+                .collect::<Vec<_>>()
+        }, s);
+
+        let history = history.clone();
+        let contest_type = contest_type.clone();
+        let url = url.clone();
+        let session = session.clone();
+        let contest_data = contest_data.clone();
+
+        VStack::hetero_options(
+            VStackOptions::default()
+                .align(HorizontalAlignment::Leading)
+                .spacing(0.0)
+        )
+            .push(
+                text(title.clone())
+                    .bold()
+                    .text_size(18)
+                    .padding_edge(5, edge::UP | edge::DOWN)
+            )
+            .push(
+                rows.sig_vmap_options(move |(entry, bookmarked), _s| {
+                    entry_row(
+                        entry, *bookmarked, history.clone(),
+                        contest_type.clone(), url.clone(), session.clone(), contest_data.clone()
+                    )
+                }, VStackOptions::default().spacing(0.0).align(HorizontalAlignment::Leading))
+            )
+            .push(divider())
+    })
+}
+
+// shown while no contest is loaded
+pub fn start_screen(
+    contest_type: impl Binding<Filterless<Option<String>>> + Clone,
+    url: impl Binding<Filterless<String>> + Clone,
+    session: impl Binding<Filterless<WindowSession>> + Clone,
+    contest_data: impl Binding<Filterless<ScoreboardOption>> + Clone,
+) -> impl IVP {
+    let history = Store::new(History::load());
+
+    ScrollView::vertical(
+        VStack::hetero_options(
+            VStackOptions::default()
+                .align(HorizontalAlignment::Leading)
+                .spacing(0.0)
+        )
+            .push(
+                text("Select a contest above, or reopen one below")
+                    .padding_edge(5, edge::DOWN)
+            )
+            .push(section(
+                "Bookmarks", |h| h.bookmarks.clone(), history.binding(),
+                contest_type.clone(), url.clone(), session.clone(), contest_data.clone()
+            ))
+            .push(section(
+                "Recent", |h| h.recent.clone(), history.binding(),
+                contest_type, url, session, contest_data
+            ))
+            .padding(10)
+    )
+        .frame(F.unlimited_stretch())
+}
@@ -0,0 +1,73 @@
+use quarve::prelude::*;
+use quarve::state::Filterless;
+use quarve::view::text::TextModifier;
+use icpc_scoreboard::scoreboard::Scoreboard;
+use icpc_scoreboard::stats::{problem_stats, ProblemStats};
+use crate::IVP;
+use crate::views::{divider, problem_label, shown_problems};
+
+fn header() -> impl IVP {
+    hstack()
+        .push(text("Problem").bold().intrinsic(200, 24))
+        .push(text("Solved").bold().intrinsic(70, 24))
+        .push(text("Attempted").bold().intrinsic(80, 24))
+        .push(text("First solve").bold().intrinsic(260, 24))
+        .push(text("Avg tries").bold().intrinsic(80, 24))
+        .push(text("Accepted").bold().intrinsic(80, 24))
+}
+
+fn row(index: usize, name: &str, stats: &ProblemStats) -> impl IVP {
+    let first_solve = match &stats.first_solve {
+        Some((team, seconds)) => format!("{} ({} min)", team, seconds / 60),
+        None => "-".to_string()
+    };
+    let average = stats.average_attempts()
+        .map(|a| format!("{:.1}", a))
+        .unwrap_or_else(|| "-".to_string());
+    let acceptance = stats.acceptance_rate()
+        .map(|r| format!("{:.0}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string());
+
+    hstack()
+        .push(text(format!("{} {}", problem_label(index), name)).bold().intrinsic(200, 24))
+        .push(text(stats.solved.to_string()).intrinsic(70, 24))
+        .push(text(stats.attempted.to_string()).intrinsic(80, 24))
+        .push(text(first_solve).intrinsic(260, 24))
+        .push(text(average).intrinsic(80, 24))
+        .push(text(acceptance).intrinsic(80, 24))
+}
+
+// per problem statistics at the current replay minute
+pub fn stats_table(sb: &Scoreboard, timer: impl Binding<Filterless<usize>> + Clone, spoiler_safe: bool) -> impl IVP {
+    let sb = sb.clone();
+
+    let rows = ivp_using(move |_, s| {
+        timer.map(move |time| {
+            problem_stats(&sb, *time)
+                .into_iter()
+                .take(shown_problems(&sb, *time, spoiler_safe))
+                .enumerate()
+                .map(|(i, stats)| (i, sb.problem_name(i).unwrap_or("").to_string(), stats))
+                .collect::<Vec<_>>()
+        }, s)
+            .sig_vmap_options(|(i, name, stats), _s| row(*i, name, stats), VStackOptions::default().spacing(0.0))
+    });
+
+    VStack::hetero_options(
+        VStackOptions::default()
+            .align(HorizontalAlignment::Leading)
+            .spacing(0.0)
+    )
+        .push(
+            VStack::hetero_options(
+                VStackOptions::default()
+                    .align(HorizontalAlignment::Leading)
+                    .spacing(0.0)
+            )
+                .push(header())
+                .push(rows)
+                .text_size(12)
+                .padding(5)
+        )
+        .push(divider())
+}
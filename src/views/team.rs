@@ -0,0 +1,122 @@
+use quarve::prelude::*;
+use quarve::state::Filterless;
+use quarve::state::SetAction::Set;
+use quarve::view::scroll::ScrollView;
+use quarve::view::text::{Text, TextModifier};
+use icpc_scoreboard::scoreboard::{ProblemStatus, Scoreboard};
+use icpc_scoreboard::stats::{rank_over_time, CHART_BUCKET_MINUTES};
+use crate::IVP;
+use crate::views::charts::rank_row;
+use crate::views::{divider, problem_label};
+
+// a single accepted problem in a team's timeline
+#[derive(Clone, PartialEq)]
+struct Solve {
+    problem: usize,
+    seconds: usize,
+    attempts: usize,
+    penalty: usize,
+}
+
+fn solves(sb: &Scoreboard, team: usize, elapsed_seconds: usize) -> Vec<Solve> {
+    let mut solves = sb.entries[team].problems.iter()
+        .enumerate()
+        .filter_map(|(problem, status)| {
+            let penalty = status.penalty(elapsed_seconds)?;
+            let ProblemStatus::Solved { seconds, attempts, .. } = status else {
+                return None;
+            };
+
+            Some(Solve {
+                problem,
+                seconds: *seconds,
+                attempts: *attempts,
+                penalty,
+            })
+        })
+        .collect::<Vec<_>>();
+    solves.sort_by_key(|s| s.seconds);
+
+    solves
+}
+
+fn solve_row(solve: &Solve) -> impl IVP {
+    let rejected = solve.attempts.saturating_sub(1);
+    let minutes = solve.seconds / 60;
+
+    hstack()
+        .push(text(format!("{}:{:0>2}", minutes, solve.seconds % 60)).intrinsic(70, 22))
+        .push(text(problem_label(solve.problem)).bold().intrinsic(30, 22))
+        .push(text(format!("{} rejected", rejected)).intrinsic(90, 22))
+        .push(text(format!("{} + {} × 20 = {}", minutes, rejected, solve.penalty)).intrinsic(160, 22))
+}
+
+// timeline, penalty breakdown and rank history of a single team at the current replay minute
+pub fn team_detail(
+    sb: &Scoreboard,
+    team: usize,
+    timer: impl Binding<Filterless<usize>> + Clone,
+    selected: impl Binding<Filterless<Option<usize>>> + Clone
+) -> impl IVP {
+    let name = sb.entries[team].team.clone();
+
+    let summary_sb = sb.clone();
+    let summary_timer = timer.clone();
+    let summary = ivp_using(move |_, s| {
+        Text::from_signal(summary_timer.map(move |time| {
+            let rank = summary_sb.ranking(*time)
+                .iter()
+                .position(|i| *i == team)
+                .map_or(0, |r| r + 1);
+            let (solved, penalty) = summary_sb.entries[team].score(*time);
+
+            format!("Rank {}, {} solved, {} penalty", rank, -solved, penalty)
+        }, s))
+    });
+
+    let timeline_sb = sb.clone();
+    let timeline_timer = timer.clone();
+    let timeline = ivp_using(move |_, s| {
+        timeline_timer.map(move |time| solves(&timeline_sb, team, *time), s)
+            .sig_vmap_options(|solve, _s| solve_row(solve), VStackOptions::default().spacing(0.0).align(HorizontalAlignment::Leading))
+    });
+
+    let rank_sb = sb.clone();
+    let ranks = ivp_using(move |_, s| {
+        let num_teams = rank_sb.entries.len();
+        let name = rank_sb.entries[team].team.clone();
+        timer.map(move |time| vec![rank_over_time(&rank_sb, team, *time, CHART_BUCKET_MINUTES)], s)
+            .sig_vmap(move |ranks, _s| rank_row(name.clone(), ranks, num_teams, BLUE))
+    });
+
+    ScrollView::vertical(
+        VStack::hetero_options(
+            VStackOptions::default()
+                .align(HorizontalAlignment::Leading)
+                .spacing(5.0)
+        )
+            .push(
+                hstack()
+                    .push(
+                        text(name)
+                            .bold()
+                            .text_size(18)
+                    )
+                    .push(
+                        button("Close", move |s| {
+                            selected.apply(Set(None), s);
+                        })
+                    )
+            )
+            .push(summary)
+            .push(divider())
+            .push(text("Timeline").bold())
+            .push(timeline)
+            .push(divider())
+            .push(text("Rank over time").bold())
+            .push(ranks)
+            .padding(10)
+    )
+        .intrinsic(540, 400)
+        .border(LIGHT_GRAY, 1)
+}